@@ -0,0 +1,70 @@
+#![allow(missing_docs, unused_crate_dependencies)]
+
+use {
+    criterion::{Criterion, Throughput, criterion_group, criterion_main},
+    octs::{BytesMut, Read, Write},
+    std::hint::black_box,
+};
+
+const COUNT: usize = 100_000;
+
+#[allow(clippy::cast_precision_loss)]
+fn values() -> Vec<f32> {
+    (0..COUNT).map(|i| i as f32 * 0.5).collect()
+}
+
+fn write(c: &mut Criterion) {
+    let values = values();
+    let mut group = c.benchmark_group("write [f32]");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    group.bench_function("each", |b| {
+        let mut buf = BytesMut::with_capacity(COUNT * 4);
+        b.iter(|| {
+            buf.clear();
+            for v in &values {
+                buf.write(v).unwrap();
+            }
+            black_box(&buf);
+        });
+    });
+    group.bench_function("write_slice", |b| {
+        let mut buf = BytesMut::with_capacity(COUNT * 4);
+        b.iter(|| {
+            buf.clear();
+            buf.write_slice(&values).unwrap();
+            black_box(&buf);
+        });
+    });
+    group.finish();
+}
+
+fn read(c: &mut Criterion) {
+    let mut buf = BytesMut::new();
+    buf.write_slice(&values()).unwrap();
+    let bytes = buf.freeze();
+
+    let mut group = c.benchmark_group("read [f32]");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    group.bench_function("each", |b| {
+        let mut dst = vec![0.0f32; COUNT];
+        b.iter(|| {
+            let mut src = &bytes[..];
+            for v in &mut dst {
+                *v = src.read().unwrap();
+            }
+            black_box(&dst);
+        });
+    });
+    group.bench_function("read_into", |b| {
+        let mut dst = vec![0.0f32; COUNT];
+        b.iter(|| {
+            let mut src = &bytes[..];
+            src.read_into(&mut dst).unwrap();
+            black_box(&dst);
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, write, read);
+criterion_main!(benches);
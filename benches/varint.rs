@@ -0,0 +1,93 @@
+#![allow(missing_docs, unused_crate_dependencies)]
+
+use {
+    criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main},
+    octs::{Buf, Bytes, BytesMut, Read, VarInt, Write},
+    std::hint::black_box,
+};
+
+const COUNT: usize = 10_000;
+
+fn encode_all(values: impl Iterator<Item = u64>) -> Bytes {
+    let mut buf = BytesMut::new();
+    for v in values {
+        buf.write(VarInt(v)).unwrap();
+    }
+    buf.freeze()
+}
+
+/// Values with a pseudo-random mix of encoded lengths from 1 to 8 bytes.
+fn mixed() -> impl Iterator<Item = u64> {
+    // xorshift64
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..COUNT).map(move |_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let bits = 7 * (1 + state % 8);
+        (state >> 8) & ((1 << bits) - 1)
+    })
+}
+
+/// Buffer which only exposes one byte at a time, forcing the byte-by-byte
+/// decoding path.
+struct OneByte(Bytes);
+
+impl Buf for OneByte {
+    fn remaining(&self) -> usize {
+        self.0.remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.0.chunk()[..self.0.remaining().min(1)]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.0.advance(cnt);
+    }
+}
+
+fn decode(c: &mut Criterion) {
+    let inputs = [
+        ("1 byte", encode_all((0..COUNT as u64).map(|i| i % 0x80))),
+        ("3 bytes", encode_all((0..COUNT as u64).map(|i| 0x4000 + i))),
+        (
+            "5 bytes",
+            encode_all((0..COUNT as u64).map(|i| 0x8_0000_0000 - i)),
+        ),
+        (
+            "10 bytes",
+            encode_all((0..COUNT as u64).map(|i| u64::MAX - i)),
+        ),
+        ("mixed", encode_all(mixed())),
+    ];
+
+    let mut group = c.benchmark_group("decode VarInt<u64>");
+    group.throughput(Throughput::Elements(COUNT as u64));
+    for (name, bytes) in inputs {
+        group.bench_with_input(BenchmarkId::new("contiguous", name), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut buf = bytes.clone();
+                while buf.has_remaining() {
+                    black_box(buf.read::<VarInt<u64>>().unwrap());
+                }
+            });
+        });
+        group.bench_with_input(
+            BenchmarkId::new("byte by byte", name),
+            &bytes,
+            |b, bytes| {
+                b.iter(|| {
+                    let mut buf = OneByte(bytes.clone());
+                    while buf.has_remaining() {
+                        black_box(buf.read::<VarInt<u64>>().unwrap());
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
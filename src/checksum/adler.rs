@@ -0,0 +1,66 @@
+use super::Checksum;
+
+const MOD: u32 = 65521;
+
+// largest n such that 255n(n+1)/2 + (n+1)(MOD-1) <= u32::MAX,
+// so we can defer taking the modulo for this many bytes
+const NMAX: usize = 5552;
+
+/// Adler-32 checksum, as used by zlib.
+///
+/// This is faster to compute than [`Crc32`], but is weaker for short inputs.
+///
+/// [`Crc32`]: super::Crc32
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    #[inline]
+    fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(NMAX) {
+            for &byte in chunk {
+                self.a += u32::from(byte);
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> Self::Output {
+        (self.b << 16) | self.a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_value() {
+        assert_eq!(0x11e6_0398, Adler32::checksum(b"Wikipedia"));
+        assert_eq!(1, Adler32::checksum(b""));
+    }
+
+    #[test]
+    fn long_input() {
+        static BYTES: [u8; 3 * NMAX + 17] = [0xff; 3 * NMAX + 17];
+        let mut expected = Adler32::default();
+        for byte in BYTES {
+            expected.update(&[byte]);
+        }
+        assert_eq!(expected.finish(), Adler32::checksum(&BYTES));
+    }
+}
@@ -0,0 +1,102 @@
+use super::Checksum;
+
+const fn make_table(poly: u32) -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ poly
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+#[inline]
+fn update(table: &[u32; 256], mut crc: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        let index = (crc ^ u32::from(byte)) & 0xff;
+        crc = table[index as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+macro_rules! impl_crc {
+    ($(#[$meta:meta])* $name:ident, $table:ident, $poly:expr) => {
+        static $table: [u32; 256] = make_table($poly);
+
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name {
+            crc: u32,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { crc: u32::MAX }
+            }
+        }
+
+        impl Checksum for $name {
+            type Output = u32;
+
+            #[inline]
+            fn update(&mut self, bytes: &[u8]) {
+                self.crc = update(&$table, self.crc, bytes);
+            }
+
+            #[inline]
+            fn finish(&self) -> Self::Output {
+                !self.crc
+            }
+        }
+    };
+}
+
+impl_crc!(
+    /// CRC-32 (ISO-HDLC) checksum, as used by Ethernet, zlib and PNG.
+    Crc32,
+    CRC32_TABLE,
+    0xedb8_8320
+);
+
+impl_crc!(
+    /// CRC-32C (Castagnoli) checksum, as used by iSCSI, SCTP and ext4.
+    Crc32c,
+    CRC32C_TABLE,
+    0x82f6_3b78
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(0xcbf4_3926, Crc32::checksum(b"123456789"));
+        assert_eq!(0, Crc32::checksum(b""));
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        assert_eq!(0xe306_9283, Crc32c::checksum(b"123456789"));
+        assert_eq!(0, Crc32c::checksum(b""));
+    }
+
+    #[test]
+    fn incremental() {
+        let mut crc = Crc32::default();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(Crc32::checksum(b"123456789"), crc.finish());
+    }
+}
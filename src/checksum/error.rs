@@ -0,0 +1,55 @@
+use {
+    crate::BufError,
+    core::{error::Error, fmt::Display},
+};
+
+/// Attempted to read a [`Checksummed`] value from a buffer, but the checksum
+/// computed over the value's bytes did not match the checksum stored after
+/// them.
+///
+/// This usually means that the bytes were corrupted in transit.
+///
+/// [`Checksummed`]: crate::checksum::Checksummed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChecksumMismatch;
+
+impl Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "checksum mismatch")
+    }
+}
+
+impl BufError for ChecksumMismatch {}
+
+impl Error for ChecksumMismatch {}
+
+/// Error when decoding a [`Checksummed`] value.
+///
+/// [`Checksummed`]: crate::checksum::Checksummed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChecksummedError<E> {
+    /// Failed to decode the inner value.
+    Inner(E),
+    /// See [`ChecksumMismatch`].
+    Mismatch(ChecksumMismatch),
+}
+
+impl<E: Display> Display for ChecksummedError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Inner(err) => write!(f, "{err}"),
+            Self::Mismatch(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E> BufError for ChecksummedError<E> {}
+
+impl<E: Error + 'static> Error for ChecksummedError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Inner(err) => Some(err),
+            Self::Mismatch(err) => Some(err),
+        }
+    }
+}
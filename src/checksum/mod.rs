@@ -0,0 +1,316 @@
+//! Allows appending a checksum to an encoded value, and verifying it when
+//! decoding.
+//!
+//! Wrap a value in [`Checksummed`] to encode it followed by a checksum computed
+//! over its encoded bytes. When decoding, the checksum is recomputed over the
+//! bytes consumed by the inner value, and compared against the stored
+//! checksum.
+//!
+//! # Examples
+//!
+//! ```
+//! # use octs::{BytesMut, Read, Write, checksum::{Checksummed, Crc32}};
+//! let mut buf = BytesMut::new();
+//! buf.write(Checksummed::<_, Crc32>::new(1234u32)).unwrap();
+//! // 4 bytes of value + 4 bytes of checksum
+//! assert_eq!(8, buf.len());
+//!
+//! let mut buf = buf.freeze();
+//! let Checksummed(value, _) = buf.read::<Checksummed<u32, Crc32>>().unwrap();
+//! assert_eq!(1234, value);
+//! ```
+
+mod adler;
+mod crc;
+mod error;
+
+use {
+    crate::{
+        Buf, BufMut, BufTooShort, BufTooShortOr, Decode, Encode, EncodeLen, FixedEncodeLen,
+        FixedEncodeLenHint, Read, Write,
+    },
+    bytes::buf::UninitSlice,
+    core::{convert::Infallible, fmt::Debug, marker::PhantomData},
+};
+pub use {adler::*, crc::*, error::*};
+
+/// Algorithm for computing a checksum over a sequence of bytes.
+///
+/// A new checksum computation starts from [`Default::default`], is fed bytes
+/// using [`Checksum::update`], and produces its result with
+/// [`Checksum::finish`].
+pub trait Checksum: Default {
+    /// Type of the checksum computed by this algorithm.
+    type Output: Debug
+        + Copy
+        + Eq
+        + Encode<Error = Infallible>
+        + Decode<Error = Infallible>
+        + FixedEncodeLen;
+
+    /// Feeds `bytes` into this checksum computation.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Gets the checksum of all bytes fed into this computation so far.
+    fn finish(&self) -> Self::Output;
+
+    /// Computes the checksum of `bytes` in one go.
+    #[must_use]
+    fn checksum(bytes: &[u8]) -> Self::Output {
+        let mut checksum = Self::default();
+        checksum.update(bytes);
+        checksum.finish()
+    }
+}
+
+/// Wrapper around a `T` which is encoded followed by a checksum of its encoded
+/// bytes, computed using the algorithm `C`.
+///
+/// See the [module-level documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Checksummed<T, C>(pub T, pub PhantomData<C>);
+
+impl<T, C> Checksummed<T, C> {
+    /// Wraps a value.
+    pub const fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Takes the inner value out of this wrapper.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: EncodeLen, C: Checksum> EncodeLen for Checksummed<T, C> {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.0.encode_len() + C::Output::ENCODE_LEN
+    }
+}
+
+impl<T: FixedEncodeLenHint, C: Checksum> FixedEncodeLenHint for Checksummed<T, C> {
+    const MIN_ENCODE_LEN: usize = T::MIN_ENCODE_LEN + C::Output::ENCODE_LEN;
+
+    const MAX_ENCODE_LEN: usize = T::MAX_ENCODE_LEN + C::Output::ENCODE_LEN;
+}
+
+impl<T: Decode, C: Checksum> Decode for Checksummed<T, C> {
+    type Error = ChecksummedError<T::Error>;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let mut hashing = Hashing {
+            inner: &mut src,
+            checksum: C::default(),
+        };
+        let value = hashing
+            .read::<T>()
+            .map_err(|e| e.map_or(ChecksummedError::Inner))?;
+        let computed = hashing.checksum.finish();
+
+        let stored = src.read::<C::Output>()?;
+        if computed == stored {
+            Ok(Self::new(value))
+        } else {
+            Err(ChecksummedError::Mismatch(ChecksumMismatch).into())
+        }
+    }
+}
+
+impl<T: EncodeLen, C: Checksum> Encode for Checksummed<T, C> {
+    type Error = T::Error;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        if dst.remaining_mut() < self.encode_len() {
+            return Err(BufTooShortOr::TooShort);
+        }
+
+        let mut hashing = HashingMut {
+            inner: &mut dst,
+            checksum: C::default(),
+            scratch: [0; SCRATCH_LEN],
+        };
+        hashing.write(&self.0)?;
+        let checksum = hashing.checksum.finish();
+        dst.write(checksum).map_err(BufTooShort::from)?;
+        Ok(())
+    }
+}
+
+/// [`Buf`] which feeds every byte that is consumed from the inner buffer into
+/// a checksum.
+#[derive(Debug)]
+struct Hashing<B, C> {
+    inner: B,
+    checksum: C,
+}
+
+impl<B: Buf, C: Checksum> Buf for Hashing<B, C> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.inner.chunk()
+    }
+
+    #[inline]
+    fn advance(&mut self, mut cnt: usize) {
+        while cnt > 0 {
+            let chunk = self.inner.chunk();
+            let n = chunk.len().min(cnt);
+            if n == 0 {
+                // let the inner buffer decide how to handle advancing too far
+                self.inner.advance(cnt);
+                return;
+            }
+            self.checksum.update(&chunk[..n]);
+            self.inner.advance(n);
+            cnt -= n;
+        }
+    }
+}
+
+const SCRATCH_LEN: usize = 64;
+
+/// [`BufMut`] which feeds every byte that is written into the inner buffer
+/// into a checksum.
+#[derive(Debug)]
+struct HashingMut<B, C> {
+    inner: B,
+    checksum: C,
+    // bytes written through `chunk_mut` land here first, so that we can read
+    // them back before passing them on to `inner`
+    scratch: [u8; SCRATCH_LEN],
+}
+
+// SAFETY: `chunk_mut` only ever exposes `scratch`, which is always
+// initialized, and `advance_mut` never reads past it.
+unsafe impl<B: BufMut, C: Checksum> BufMut for HashingMut<B, C> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        self.inner.remaining_mut()
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        let bytes = &self.scratch[..cnt];
+        self.checksum.update(bytes);
+        self.inner.put_slice(bytes);
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let len = self.remaining_mut().min(SCRATCH_LEN);
+        UninitSlice::new(&mut self.scratch[..len])
+    }
+
+    #[inline]
+    fn put_slice(&mut self, src: &[u8]) {
+        self.checksum.update(src);
+        self.inner.put_slice(src);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Bytes, BytesMut, StackBuf, test::*},
+    };
+
+    #[test]
+    fn round_trip() {
+        hint_round_trip(&Checksummed::<_, Crc32>::new(1234u32));
+        hint_round_trip(&Checksummed::<_, Crc32c>::new(true));
+        hint_round_trip(&Checksummed::<_, Adler32>::new(crate::VarInt(300u64)));
+    }
+
+    #[test]
+    fn wire_format() {
+        let mut buf = BytesMut::new();
+        buf.write(Checksummed::<_, Crc32>::new(0x3132_3334u32))
+            .unwrap();
+        // b"1234", then CRC-32 of b"1234"
+        assert_eq!(&[0x31, 0x32, 0x33, 0x34, 0x9b, 0xe3, 0xe0, 0xa3], &buf[..]);
+    }
+
+    #[test]
+    fn encode_too_short() {
+        let mut buf = StackBuf::<6>::new();
+        buf.write(1u8).unwrap();
+        assert_eq!(
+            Err(BufTooShortOr::TooShort),
+            buf.write(Checksummed::<_, Crc32>::new(0x1234u16))
+        );
+        // nothing is written if there isn't space for the value and checksum
+        assert_eq!(&[1], &buf[..]);
+    }
+
+    #[test]
+    fn encode_large() {
+        // larger than the scratch buffer
+        let value = Bytes::from(alloc::vec![0xab; 300]);
+        let mut buf = BytesMut::new();
+        buf.write(Checksummed::<_, Crc32c>::new(value.clone()))
+            .unwrap();
+
+        let mut expected = BytesMut::new();
+        expected.write(&value).unwrap();
+        let checksum = Crc32c::checksum(&expected);
+        expected.write(checksum).unwrap();
+        assert_eq!(expected, buf);
+    }
+
+    #[test]
+    fn hash_chunk_mut() {
+        let mut buf = BytesMut::new();
+        let mut hashing = HashingMut {
+            inner: &mut buf,
+            checksum: Adler32::default(),
+            scratch: [0; SCRATCH_LEN],
+        };
+        // written through `chunk_mut`, across several chunks
+        hashing.put_bytes(0xab, 200);
+        let checksum = hashing.checksum.finish();
+
+        assert_eq!(&[0xab; 200][..], &buf[..]);
+        assert_eq!(Adler32::checksum(&[0xab; 200]), checksum);
+    }
+
+    #[test]
+    fn detect_corruption() {
+        let mut buf = BytesMut::new();
+        buf.write(Checksummed::<_, Crc32>::new(0x1234_5678u32))
+            .unwrap();
+        buf[1] ^= 0x10;
+
+        assert_eq!(
+            BufTooShortOr::Or(ChecksummedError::Mismatch(ChecksumMismatch)),
+            buf.freeze().read::<Checksummed<u32, Crc32>>().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn read_non_contiguous() {
+        let mut buf = BytesMut::new();
+        buf.write(Checksummed::<_, Crc32c>::new(u64::MAX - 1))
+            .unwrap();
+        let buf = buf.freeze();
+
+        let mut src = buf.slice(..3).chain(buf.slice(3..));
+        let Checksummed(value, _) = src.read::<Checksummed<u64, Crc32c>>().unwrap();
+        assert_eq!(u64::MAX - 1, value);
+        assert!(!src.has_remaining());
+    }
+
+    #[test]
+    fn decode_malformed() {
+        truncated(&Checksummed::<_, Adler32>::new(5u16));
+        byte_flips(&Checksummed::<_, Crc32>::new(crate::VarInt(u64::MAX)));
+    }
+}
@@ -0,0 +1,38 @@
+use {
+    super::{Compression, InvalidCompressed},
+    alloc::vec::Vec,
+};
+
+/// Raw [DEFLATE] compression, provided by [`miniz_oxide`].
+///
+/// `LEVEL` is the compression level, from 0 (no compression) to 10 (best
+/// compression). Defaults to 6, which is a good balance between speed and
+/// compression ratio. The level has no effect on decompression.
+///
+/// [DEFLATE]: https://datatracker.ietf.org/doc/html/rfc1951
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Deflate<const LEVEL: u8 = 6>;
+
+impl<const LEVEL: u8> Compression for Deflate<LEVEL> {
+    fn compress(src: &[u8]) -> Vec<u8> {
+        miniz_oxide::deflate::compress_to_vec(src, LEVEL)
+    }
+
+    fn decompress(src: &[u8], len: usize) -> Result<Vec<u8>, InvalidCompressed> {
+        match miniz_oxide::inflate::decompress_to_vec_with_limit(src, len) {
+            Ok(dst) if dst.len() == len => Ok(dst),
+            _ => Err(InvalidCompressed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm() {
+        super::super::tests::algorithm::<Deflate>();
+        super::super::tests::algorithm::<Deflate<10>>();
+    }
+}
@@ -0,0 +1,87 @@
+use {
+    crate::BufError,
+    core::{error::Error, fmt::Display},
+};
+
+/// Attempted to read a [`Compressed`] value from a buffer, but the value would
+/// have decompressed into more bytes than the maximum allowed length.
+///
+/// This is also returned when attempting to write a value which is larger than
+/// that length.
+///
+/// This protects against decompression bombs - small payloads which expand
+/// into a huge amount of memory when decompressed.
+///
+/// [`Compressed`]: crate::compress::Compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecompressedTooLarge;
+
+impl Display for DecompressedTooLarge {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "decompressed payload too large")
+    }
+}
+
+impl BufError for DecompressedTooLarge {}
+
+impl Error for DecompressedTooLarge {}
+
+/// Attempted to read a [`Compressed`] value from a buffer, but the compressed
+/// payload was malformed.
+///
+/// This may mean that:
+/// * the length prefixes of the payload could not be read
+/// * the compressed bytes could not be decompressed
+/// * the decompressed bytes did not match the declared decompressed length
+/// * the decompressed bytes did not contain exactly one inner value
+///
+/// [`Compressed`]: crate::compress::Compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidCompressed;
+
+impl Display for InvalidCompressed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid compressed payload")
+    }
+}
+
+impl BufError for InvalidCompressed {}
+
+impl Error for InvalidCompressed {}
+
+/// Error when encoding or decoding a [`Compressed`] value.
+///
+/// [`Compressed`]: crate::compress::Compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressedError<E> {
+    /// Failed to encode or decode the inner value.
+    Inner(E),
+    /// See [`DecompressedTooLarge`].
+    TooLarge(DecompressedTooLarge),
+    /// See [`InvalidCompressed`].
+    ///
+    /// This is only returned when decoding.
+    Invalid(InvalidCompressed),
+}
+
+impl<E: Display> Display for CompressedError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Inner(err) => write!(f, "{err}"),
+            Self::TooLarge(err) => write!(f, "{err}"),
+            Self::Invalid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E> BufError for CompressedError<E> {}
+
+impl<E: Error + 'static> Error for CompressedError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Inner(err) => Some(err),
+            Self::TooLarge(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use {
+    super::{Compression, InvalidCompressed},
+    alloc::{vec, vec::Vec},
+};
+
+/// [LZ4] block compression, provided by [`lz4_flex`].
+///
+/// LZ4 trades compression ratio for very fast compression and decompression,
+/// which makes it a good fit for real-time traffic.
+///
+/// [LZ4]: https://lz4.org/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Lz4;
+
+impl Compression for Lz4 {
+    fn compress(src: &[u8]) -> Vec<u8> {
+        lz4_flex::block::compress(src)
+    }
+
+    fn decompress(src: &[u8], len: usize) -> Result<Vec<u8>, InvalidCompressed> {
+        let mut dst = vec![0; len];
+        match lz4_flex::block::decompress_into(src, &mut dst) {
+            Ok(n) if n == len => Ok(dst),
+            _ => Err(InvalidCompressed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm() {
+        super::super::tests::algorithm::<Lz4>();
+    }
+}
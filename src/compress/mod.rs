@@ -0,0 +1,261 @@
+//! Allows compressing the encoded form of a value.
+//!
+//! Wrap a value in [`Compressed`] to encode it, compress the resulting bytes,
+//! and write them into a length-prefixed envelope. When decoding, the envelope
+//! is decompressed and the inner value is decoded from the decompressed bytes.
+//!
+//! Compression algorithms are implemented via [`Compression`]. The inbuilt
+//! algorithms are each gated behind a feature:
+//! * `lz4` - `Lz4`, using [LZ4](https://lz4.org/)
+//! * `deflate` - `Deflate`, using [DEFLATE](https://datatracker.ietf.org/doc/html/rfc1951)
+//!
+//! # Wire format
+//!
+//! * decompressed length as a [`VarInt<usize>`]
+//! * compressed length as a [`VarInt<usize>`]
+//! * compressed bytes
+//!
+//! # Decompression bombs
+//!
+//! Since the decompressed length is read from an untrusted source, decoding
+//! checks it against the `MAX_LEN` parameter of [`Compressed`] before
+//! allocating any memory for decompression. If it is larger, decoding fails
+//! with [`DecompressedTooLarge`].
+//!
+//! Encoding a value which is larger than `MAX_LEN` also fails with
+//! [`DecompressedTooLarge`], since the same type would never be able to decode
+//! it.
+
+#[cfg(feature = "deflate")]
+mod deflate;
+mod error;
+#[cfg(feature = "lz4")]
+mod lz4;
+
+#[cfg(feature = "deflate")]
+pub use deflate::*;
+pub use error::*;
+#[cfg(feature = "lz4")]
+pub use lz4::*;
+use {
+    crate::{
+        Buf, BufTooShort, BufTooShortOr, Bytes, BytesMut, CountingWriter, Decode, Encode,
+        EncodeLen, Read, VarInt, Write,
+    },
+    alloc::vec::Vec,
+    core::marker::PhantomData,
+};
+
+/// Default value of the `MAX_LEN` parameter of [`Compressed`].
+///
+/// This is 1 MiB.
+pub const DEFAULT_MAX_LEN: usize = 1024 * 1024;
+
+/// Algorithm for compressing and decompressing bytes.
+pub trait Compression {
+    /// Compresses `src` into a new buffer.
+    fn compress(src: &[u8]) -> Vec<u8>;
+
+    /// Decompresses `src` into a new buffer of exactly `len` bytes.
+    ///
+    /// Implementations must not allocate more than `len` bytes for the output.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `src` could not be decompressed, or if it does not decompress
+    /// into exactly `len` bytes.
+    fn decompress(src: &[u8], len: usize) -> Result<Vec<u8>, InvalidCompressed>;
+}
+
+/// Wrapper around a `T` which is encoded in a compressed, length-prefixed
+/// envelope, compressed using the algorithm `A`.
+///
+/// `MAX_LEN` is the maximum number of decompressed bytes which will be
+/// accepted when decoding.
+///
+/// See the [module-level documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Compressed<T, A, const MAX_LEN: usize = DEFAULT_MAX_LEN>(pub T, pub PhantomData<A>);
+
+impl<T, A, const MAX_LEN: usize> Compressed<T, A, MAX_LEN> {
+    /// Wraps a value.
+    pub const fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Takes the inner value out of this wrapper.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Decode, A: Compression, const MAX_LEN: usize> Decode for Compressed<T, A, MAX_LEN> {
+    type Error = CompressedError<T::Error>;
+
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let VarInt(len) = src
+            .read::<VarInt<usize>>()
+            .map_err(|e| e.map_or(|_| CompressedError::Invalid(InvalidCompressed)))?;
+        if len > MAX_LEN {
+            return Err(CompressedError::TooLarge(DecompressedTooLarge).into());
+        }
+
+        let VarInt(compressed_len) = src
+            .read::<VarInt<usize>>()
+            .map_err(|e| e.map_or(|_| CompressedError::Invalid(InvalidCompressed)))?;
+        let compressed = src.read_next(compressed_len)?;
+        let decompressed = A::decompress(&compressed, len).map_err(CompressedError::Invalid)?;
+
+        // running out of bytes in here means the payload is malformed,
+        // not that `src` is too short
+        let mut decompressed = Bytes::from(decompressed);
+        let value = decompressed.read::<T>().map_err(|e| match e {
+            BufTooShortOr::TooShort => CompressedError::Invalid(InvalidCompressed),
+            BufTooShortOr::Or(e) => CompressedError::Inner(e),
+        })?;
+        if decompressed.has_remaining() {
+            return Err(CompressedError::Invalid(InvalidCompressed).into());
+        }
+        Ok(Self::new(value))
+    }
+}
+
+impl<T: Encode, A: Compression, const MAX_LEN: usize> EncodeLen for Compressed<T, A, MAX_LEN> {
+    fn encode_len(&self) -> usize {
+        // the compressed length is only known after compressing, so this has
+        // to do a dry run - values which fail to encode will fail again when
+        // actually encoded, so don't panic on them like the default does
+        let mut counter = CountingWriter::new();
+        _ = self.encode(&mut counter);
+        counter.count()
+    }
+}
+
+impl<T: Encode, A: Compression, const MAX_LEN: usize> Encode for Compressed<T, A, MAX_LEN> {
+    type Error = CompressedError<T::Error>;
+
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        let mut decompressed = BytesMut::new();
+        decompressed
+            .write(&self.0)
+            .map_err(|e| e.map_or(CompressedError::Inner))?;
+        if decompressed.len() > MAX_LEN {
+            return Err(CompressedError::TooLarge(DecompressedTooLarge).into());
+        }
+        let compressed = A::compress(&decompressed);
+
+        let len = VarInt(decompressed.len());
+        let compressed_len = VarInt(compressed.len());
+        if dst.remaining_mut() < len.encode_len() + compressed_len.encode_len() + compressed.len() {
+            return Err(BufTooShortOr::TooShort);
+        }
+        dst.write(len).map_err(BufTooShort::from)?;
+        dst.write(compressed_len).map_err(BufTooShort::from)?;
+        dst.write_from(&compressed[..])?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, any(feature = "lz4", feature = "deflate")))]
+pub(crate) mod tests {
+    use {
+        super::*,
+        crate::{
+            VarIntTooLarge,
+            checksum::{Checksummed, Crc32},
+            test::round_trip,
+        },
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Payload(Bytes);
+
+    impl Encode for Payload {
+        type Error = core::convert::Infallible;
+
+        fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+            dst.write(VarInt(self.0.len()))?;
+            dst.write_from(self.0.clone())?;
+            Ok(())
+        }
+    }
+
+    impl Decode for Payload {
+        type Error = VarIntTooLarge;
+
+        fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+            let VarInt(len) = src.read::<VarInt<usize>>()?;
+            Ok(Self(src.read_next(len)?))
+        }
+    }
+
+    fn encode<T: Encode>(value: T) -> Bytes
+    where
+        T::Error: core::fmt::Debug,
+    {
+        let mut buf = BytesMut::new();
+        buf.write(value).unwrap();
+        buf.freeze()
+    }
+
+    pub fn algorithm<A: Compression + core::fmt::Debug + PartialEq>() {
+        for payload in [
+            Bytes::new(),
+            Bytes::from_static(b"hello world"),
+            Bytes::from(alloc::vec![0u8; 4096]),
+        ] {
+            round_trip(&Compressed::<_, A>::new(Payload(payload.clone())));
+            round_trip(&Checksummed::<_, Crc32>::new(Compressed::<_, A>::new(
+                Payload(payload),
+            )));
+        }
+
+        // compresses
+        let buf = encode(Compressed::<_, A>::new(Payload(Bytes::from(
+            alloc::vec![0u8; 4096],
+        ))));
+        assert!(buf.len() < 4096);
+
+        // too large
+        let large = Payload(Bytes::from(alloc::vec![0u8; 4096]));
+        let mut dst = BytesMut::new();
+        assert_eq!(
+            Err(BufTooShortOr::Or(CompressedError::TooLarge(
+                DecompressedTooLarge
+            ))),
+            dst.write(Compressed::<_, A, 4000>::new(&large))
+        );
+        assert!(dst.is_empty());
+        let mut buf = encode(Compressed::<_, A>::new(&large));
+        assert_eq!(
+            BufTooShortOr::Or(CompressedError::TooLarge(DecompressedTooLarge)),
+            buf.clone()
+                .read::<Compressed<Payload, A, 4000>>()
+                .unwrap_err()
+        );
+        buf.read::<Compressed<Payload, A, 4100>>().unwrap();
+
+        // malformed
+        let value = Compressed::<_, A>::new(Payload(Bytes::from_static(b"hello hello hello")));
+        crate::test::truncated(&value);
+        crate::test::byte_flips(&value);
+
+        // lies about decompressed length
+        let mut buf = encode(Compressed::<_, A>::new(Payload(Bytes::from_static(
+            b"hello world",
+        ))))
+        .to_vec();
+        buf[0] += 1;
+        assert_eq!(
+            BufTooShortOr::Or(CompressedError::Invalid(InvalidCompressed)),
+            (&buf[..]).read::<Compressed<Payload, A>>().unwrap_err()
+        );
+
+        // garbage
+        let buf = [12u8, 4, 0xff, 0xff, 0xff, 0xff];
+        assert_eq!(
+            BufTooShortOr::Or(CompressedError::Invalid(InvalidCompressed)),
+            (&buf[..]).read::<Compressed<Payload, A>>().unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use {
+    crate::{BufTooShortOr, Encode, EncodeLen, Write},
+    bytes::{BufMut, buf::UninitSlice},
+    core::any::type_name,
+};
+
+const SCRATCH_LEN: usize = 64;
+
+/// [`BufMut`] which discards all bytes written into it, but counts how many
+/// were written.
+///
+/// Use this to find out how many bytes a value encodes to, without writing it
+/// anywhere. This never runs out of space, so writing into it only fails if
+/// the value itself fails to encode.
+///
+/// This is what the default implementation of [`EncodeLen::encode_len`] uses.
+///
+/// # Examples
+///
+/// ```
+/// use octs::{CountingWriter, VarInt, Write};
+///
+/// let mut counter = CountingWriter::new();
+/// counter.write(0u32).unwrap();
+/// counter.write(VarInt(300u32)).unwrap();
+/// assert_eq!(6, counter.count());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CountingWriter {
+    count: usize,
+    // `BufMut::chunk_mut` must hand out some memory to write into, even though
+    // we don't care about what's written into it
+    scratch: [u8; SCRATCH_LEN],
+}
+
+impl CountingWriter {
+    /// Creates a writer with a count of 0.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            scratch: [0; SCRATCH_LEN],
+        }
+    }
+
+    /// Gets how many bytes have been written into this writer.
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Resets the count back to 0.
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+impl Default for CountingWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: `chunk_mut` only ever exposes `scratch`, which is always
+// initialized, and the bytes written into it are never read.
+unsafe impl BufMut for CountingWriter {
+    fn remaining_mut(&self) -> usize {
+        usize::MAX - self.count
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.count = self
+            .count
+            .checked_add(cnt)
+            .expect("cannot advance past `remaining_mut`");
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let len = self.remaining_mut().min(SCRATCH_LEN);
+        UninitSlice::new(&mut self.scratch[..len])
+    }
+
+    fn put_slice(&mut self, src: &[u8]) {
+        self.count = self
+            .count
+            .checked_add(src.len())
+            .expect("cannot put more than `remaining_mut` bytes");
+    }
+
+    fn put_bytes(&mut self, _: u8, cnt: usize) {
+        self.count = self
+            .count
+            .checked_add(cnt)
+            .expect("cannot put more than `remaining_mut` bytes");
+    }
+}
+
+/// Wrapper around a value which checks, in debug builds, that the value's
+/// [`EncodeLen::encode_len`] is equal to how many bytes [`Encode::encode`]
+/// actually wrote.
+///
+/// Hand-written [`EncodeLen`] implementations can easily drift out of sync
+/// with their [`Encode`] implementation, which can cause subtle bugs in code
+/// which relies on the length, such as length prefixes. Wrap a value in this
+/// type when writing it to catch these mismatches as early as possible. In
+/// release builds, this just encodes the inner value.
+///
+/// [`Write::write`] can't do this check by itself, since it accepts any
+/// [`Encode`] type, not only ones which implement [`EncodeLen`].
+///
+/// # Panics
+///
+/// In debug builds, encoding panics if the number of bytes written does not
+/// match the inner value's [`EncodeLen::encode_len`].
+///
+/// # Examples
+///
+/// ```should_panic
+/// use octs::{BufTooShortOr, BytesMut, CheckEncodeLen, Encode, EncodeLen, Write};
+///
+/// struct Header(u32);
+///
+/// impl EncodeLen for Header {
+///     fn encode_len(&self) -> usize {
+///         2 // oops, a `u32` takes 4 bytes
+///     }
+/// }
+///
+/// impl Encode for Header {
+///     type Error = core::convert::Infallible;
+///
+///     fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+///         dst.write(self.0)
+///     }
+/// }
+///
+/// let mut buf = BytesMut::new();
+/// buf.write(CheckEncodeLen(Header(1))).unwrap(); // panics
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CheckEncodeLen<T: ?Sized>(pub T);
+
+impl<T: EncodeLen + ?Sized> EncodeLen for CheckEncodeLen<T> {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.0.encode_len()
+    }
+}
+
+impl<T: EncodeLen + ?Sized> Encode for CheckEncodeLen<T> {
+    type Error = T::Error;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        let remaining_before = dst.remaining_mut();
+        self.0.encode(&mut dst)?;
+        let written = remaining_before - dst.remaining_mut();
+        debug_assert_eq!(
+            self.0.encode_len(),
+            written,
+            "`encode_len` of `{}` does not match the number of bytes written by `encode`",
+            type_name::<T>()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{Bytes, BytesMut, VarInt},
+        core::convert::Infallible,
+    };
+
+    struct DryRun(VarInt<u64>, bool);
+
+    impl Encode for DryRun {
+        type Error = Infallible;
+
+        fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+            dst.write(self.0)?;
+            dst.write(self.1)?;
+            dst.write_from(&[0u8; 100][..])?;
+            Ok(())
+        }
+    }
+
+    // uses the default, dry-run implementation
+    impl EncodeLen for DryRun {}
+
+    #[test]
+    fn counts() {
+        let mut counter = CountingWriter::new();
+        counter.write(0u8).unwrap();
+        counter.write_from(&[0u8; 1000][..]).unwrap();
+        counter.put_bytes(0, 10);
+        counter.write_slice(&[0u64; 3]).unwrap();
+        assert_eq!(1 + 1000 + 10 + 24, counter.count());
+
+        counter.reset();
+        assert_eq!(0, counter.count());
+    }
+
+    #[test]
+    #[should_panic = "fails to encode"]
+    fn default_encode_len_fails() {
+        struct Fails;
+
+        impl Encode for Fails {
+            type Error = ();
+
+            fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+                dst.write(0u8).map_err(crate::BufTooShort::from)?;
+                Err(BufTooShortOr::Or(()))
+            }
+        }
+
+        impl EncodeLen for Fails {}
+
+        _ = Fails.encode_len();
+    }
+
+    #[test]
+    fn default_encode_len() {
+        let value = DryRun(VarInt(300), true);
+        assert_eq!(2 + 1 + 100, value.encode_len());
+
+        let encode_len = value.encode_len();
+        let mut buf = BytesMut::new();
+        buf.write(CheckEncodeLen(value)).unwrap();
+        assert_eq!(encode_len, buf.len());
+    }
+
+    #[test]
+    fn check_passes() {
+        let mut buf = BytesMut::new();
+        buf.write(CheckEncodeLen(VarInt(u64::MAX))).unwrap();
+        buf.write(CheckEncodeLen(Bytes::from_static(b"hello")))
+            .unwrap();
+        assert_eq!(10 + 6, buf.len());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic = "does not match"]
+    fn check_fails() {
+        struct Wrong;
+
+        impl EncodeLen for Wrong {
+            fn encode_len(&self) -> usize {
+                1
+            }
+        }
+
+        impl Encode for Wrong {
+            type Error = Infallible;
+
+            fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+                dst.write(0u16)
+            }
+        }
+
+        let mut buf = BytesMut::new();
+        _ = buf.write(CheckEncodeLen(Wrong));
+    }
+}
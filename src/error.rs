@@ -101,3 +101,66 @@ impl<E: BufError> From<BufTooShortOr<Infallible>> for BufTooShortOr<E> {
         value.map_or(|_| unreachable!())
     }
 }
+
+/// Decoded a value from a section of a [`Read`] using [`Read::read_section`],
+/// but the value did not consume the entire section.
+///
+/// [`Read`]: crate::Read
+/// [`Read::read_section`]: crate::Read::read_section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrailingBytes {
+    /// Number of bytes left over in the section after decoding the value.
+    pub remaining: usize,
+}
+
+impl Display for TrailingBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} trailing bytes left in section", self.remaining)
+    }
+}
+
+impl BufError for TrailingBytes {}
+
+impl Error for TrailingBytes {}
+
+/// Error when decoding a value from a section of a [`Read`] using
+/// [`Read::read_section`].
+///
+/// [`Read`]: crate::Read
+/// [`Read::read_section`]: crate::Read::read_section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SectionError<E> {
+    /// See [`TrailingBytes`].
+    TrailingBytes(TrailingBytes),
+    /// Value tried to read past the end of the section.
+    ///
+    /// The buffer may have more bytes after the section, but a value read
+    /// with [`Read::read_section`] is never allowed to read them.
+    ///
+    /// [`Read::read_section`]: crate::Read::read_section
+    Overrun,
+    /// Failed to decode the value.
+    Inner(E),
+}
+
+impl<E: Display> Display for SectionError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TrailingBytes(err) => write!(f, "{err}"),
+            Self::Overrun => write!(f, "read past the end of section"),
+            Self::Inner(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E> BufError for SectionError<E> {}
+
+impl<E: Error + 'static> Error for SectionError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TrailingBytes(err) => Some(err),
+            Self::Overrun => None,
+            Self::Inner(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use {
+    crate::{Buf, BufTooShortOr, Decode, Read},
+    alloc::{format, rc::Rc, string::String, vec::Vec},
+    core::{
+        cell::{Ref, RefCell},
+        fmt::{self, Debug, Display},
+        ops::Range,
+    },
+};
+
+/// Wraps a [`Buf`] and records which byte ranges were consumed by values that
+/// you label as you decode them.
+///
+/// This is useful for understanding how a packet was decoded, in a similar way
+/// to the packet details pane in Wireshark.
+///
+/// Values are recorded by reading them through [`Annotator::read_labelled`],
+/// and related values can be nested under a parent node using
+/// [`Annotator::group`]. Since this type also implements [`Buf`], you can still
+/// pass it into any function which expects a [`Read`], and the bytes consumed
+/// this way are recorded as [`AnnotationValue::Unlabelled`] nodes, so no
+/// consumed bytes are missing from the tree.
+///
+/// A [`Decode`] impl only sees an `impl Read`, so to break a value down into
+/// the values it is made of, its [`Decode`] impl must opt in by reading its
+/// fields with [`ReadLabelled::read_labelled`]. When the value is read with
+/// [`Annotator::read_labelled`], these fields are recorded as child nodes of
+/// the value. See [`ReadLabelled`] for details.
+///
+/// Use [`Annotator::tree`] to render the recorded annotations.
+///
+/// # Examples
+///
+/// ```
+/// # use octs::{inspect::Annotator, Read, VarInt};
+/// let mut src = Annotator::new(&[0x00, 0x05, 0x01, 0xac, 0x02, 0xff][..]);
+/// src.group("header", |src| {
+///     src.read_labelled::<u16>("packet_id")?;
+///     src.read_labelled::<bool>("reliable")
+/// })
+/// .unwrap();
+/// src.read_labelled::<VarInt<u32>>("payload_len").unwrap();
+/// // not labelled
+/// Read::read::<u8>(&mut src).unwrap();
+///
+/// assert_eq!(
+///     "header [0..3]\n\
+///     \x20 packet_id: 5 [0..2]\n\
+///     \x20 reliable: true [2..3]\n\
+///     payload_len: VarInt(300) [3..5]\n\
+///     <unlabelled> [5..6]\n",
+///     src.tree().to_string(),
+/// );
+/// ```
+///
+/// [`Read`]: crate::Read
+/// [`ReadLabelled`]: super::ReadLabelled
+/// [`ReadLabelled::read_labelled`]: super::ReadLabelled::read_labelled
+#[derive(Debug)]
+pub struct Annotator<B> {
+    inner: B,
+    // shared with `ReadLabelled` while a labelled value is being decoded
+    state: Rc<RefCell<State>>,
+}
+
+/// Single node recorded by an [`Annotator`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Annotation {
+    /// Label given to this node.
+    ///
+    /// This is empty for [`AnnotationValue::Unlabelled`] nodes.
+    pub label: String,
+    /// How many parent nodes this node is nested under.
+    pub depth: usize,
+    /// Range of bytes consumed while reading this node, relative to where the
+    /// [`Annotator`] started.
+    pub range: Range<usize>,
+    /// What was read for this node.
+    pub value: AnnotationValue,
+}
+
+/// What was read for an [`Annotation`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnnotationValue {
+    /// Node was created by [`Annotator::group`].
+    Group,
+    /// Bytes were consumed outside of [`Annotator::read_labelled`], for
+    /// example by passing the [`Annotator`] into a function which takes a
+    /// [`Read`].
+    ///
+    /// Consecutive unlabelled reads are merged into a single node.
+    ///
+    /// [`Read`]: crate::Read
+    Unlabelled,
+    /// Node was created by [`Annotator::read_labelled`] or
+    /// [`ReadLabelled::read_labelled`], and decoding succeeded.
+    ///
+    /// Contains the [`Debug`] representation of the decoded value.
+    ///
+    /// [`ReadLabelled::read_labelled`]: super::ReadLabelled::read_labelled
+    Value(String),
+    /// Node was created by [`Annotator::read_labelled`] or
+    /// [`ReadLabelled::read_labelled`], but there were not enough bytes to
+    /// decode the value.
+    ///
+    /// [`ReadLabelled::read_labelled`]: super::ReadLabelled::read_labelled
+    TooShort,
+    /// Node was created by [`Annotator::read_labelled`] or
+    /// [`ReadLabelled::read_labelled`], but decoding failed.
+    ///
+    /// [`ReadLabelled::read_labelled`]: super::ReadLabelled::read_labelled
+    Failed,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct State {
+    pos: usize,
+    depth: usize,
+    // number of labelled values currently being decoded
+    labelling: usize,
+    annotations: Vec<Annotation>,
+}
+
+impl State {
+    fn start(&mut self, label: String) -> usize {
+        let index = self.annotations.len();
+        self.annotations.push(Annotation {
+            label,
+            depth: self.depth,
+            range: self.pos..self.pos,
+            value: AnnotationValue::Group,
+        });
+        self.depth += 1;
+        index
+    }
+
+    fn finish(&mut self, index: usize) {
+        self.depth -= 1;
+        self.annotations[index].range.end = self.pos;
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        let start = self.pos;
+        self.pos += cnt;
+        if self.labelling > 0 || cnt == 0 {
+            return;
+        }
+
+        match self.annotations.last_mut() {
+            Some(Annotation {
+                depth,
+                range,
+                value: AnnotationValue::Unlabelled,
+                ..
+            }) if *depth == self.depth && range.end == start => {
+                range.end = self.pos;
+            }
+            _ => self.annotations.push(Annotation {
+                label: String::new(),
+                depth: self.depth,
+                range: start..self.pos,
+                value: AnnotationValue::Unlabelled,
+            }),
+        }
+    }
+}
+
+/// Decodes a `T` from `src`, recording it as a node in `state`.
+///
+/// `src` must be reading from the [`Annotator`] which owns `state`, or from a
+/// section of it. If it turns out that `src` consumed different bytes to the
+/// annotator, such as when a value decodes a nested value from a buffer it
+/// decompressed itself, the node and its children are discarded, since their
+/// ranges would be meaningless.
+pub(super) fn read_labelled<T: Decode + Debug>(
+    state: &Rc<RefCell<State>>,
+    mut src: impl Read,
+    label: String,
+) -> Result<T, BufTooShortOr<T::Error>> {
+    let remaining = src.remaining();
+    let (index, start) = {
+        let mut state = state.borrow_mut();
+        state.labelling += 1;
+        (state.start(label), state.pos)
+    };
+
+    let result = current::with(state, || T::decode(&mut src));
+
+    let mut state = state.borrow_mut();
+    state.labelling -= 1;
+    state.finish(index);
+    if remaining - src.remaining() != state.pos - start {
+        state.annotations.truncate(index);
+        return result;
+    }
+    state.annotations[index].value = match &result {
+        Ok(value) => AnnotationValue::Value(format!("{value:?}")),
+        Err(BufTooShortOr::TooShort) => AnnotationValue::TooShort,
+        Err(BufTooShortOr::Or(_)) => AnnotationValue::Failed,
+    };
+    result
+}
+
+/// Tracks which [`Annotator`] is decoding a labelled value on this thread, so
+/// that [`ReadLabelled`] can find it.
+///
+/// [`ReadLabelled`]: super::ReadLabelled
+#[cfg(feature = "std")]
+pub(super) mod current {
+    use {super::State, alloc::rc::Rc, core::cell::RefCell, std::thread_local};
+
+    thread_local! {
+        static CURRENT: RefCell<Option<Rc<RefCell<State>>>> = const { RefCell::new(None) };
+    }
+
+    // restores the previous annotator even if decoding panics
+    struct Restore(Option<Rc<RefCell<State>>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let prev = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = prev);
+        }
+    }
+
+    pub fn with<R>(state: &Rc<RefCell<State>>, f: impl FnOnce() -> R) -> R {
+        let prev = CURRENT.with(|current| current.replace(Some(Rc::clone(state))));
+        let _restore = Restore(prev);
+        f()
+    }
+
+    pub fn get() -> Option<Rc<RefCell<State>>> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod current {
+    use {super::State, alloc::rc::Rc, core::cell::RefCell};
+
+    pub fn with<R>(_: &Rc<RefCell<State>>, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+}
+
+impl<B: Clone> Clone for Annotator<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            state: Rc::new(RefCell::new(self.state.borrow().clone())),
+        }
+    }
+}
+
+impl<B> Annotator<B> {
+    /// Creates a new annotator, reading from `inner`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            state: Rc::default(),
+        }
+    }
+
+    /// Gets the number of bytes consumed from the inner buffer so far.
+    pub fn position(&self) -> usize {
+        self.state.borrow().pos
+    }
+
+    /// Gets all annotations recorded so far, in the order they were started.
+    ///
+    /// Children always come directly after their parent.
+    pub fn annotations(&self) -> Ref<'_, [Annotation]> {
+        Ref::map(self.state.borrow(), |state| state.annotations.as_slice())
+    }
+
+    /// Takes the inner buffer out of this annotator.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    /// Renders the recorded annotations as an indented tree.
+    pub fn tree(&self) -> impl Display + '_ {
+        Tree(self.annotations())
+    }
+}
+
+impl<B: Buf> Annotator<B> {
+    /// Attempts to read the next `T` in the buffer, recording the bytes it
+    /// consumed under `label`.
+    ///
+    /// The node is recorded even if decoding fails, so you can see where
+    /// decoding stopped. Any values that `T`'s [`Decode`] impl reads with
+    /// [`ReadLabelled::read_labelled`] are recorded as children of this node.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are not enough bytes remaining in the buffer, or if a
+    /// value of `T` could not be read from the bytes in the buffer.
+    ///
+    /// [`ReadLabelled::read_labelled`]: super::ReadLabelled::read_labelled
+    pub fn read_labelled<T: Decode + Debug>(
+        &mut self,
+        label: impl Into<String>,
+    ) -> Result<T, BufTooShortOr<T::Error>> {
+        let state = Rc::clone(&self.state);
+        read_labelled(&state, self, label.into())
+    }
+
+    /// Runs `f`, recording all bytes consumed in it as a single node under
+    /// `label`.
+    ///
+    /// Any nodes recorded in `f` are nested under this node.
+    pub fn group<R>(&mut self, label: impl Into<String>, f: impl FnOnce(&mut Self) -> R) -> R {
+        let index = self.state.borrow_mut().start(label.into());
+        let result = f(self);
+        self.state.borrow_mut().finish(index);
+        result
+    }
+}
+
+impl<B: Buf> Buf for Annotator<B> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.inner.chunk()
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        self.inner.advance(cnt);
+        self.state.borrow_mut().advance(cnt);
+    }
+}
+
+struct Tree<'a>(Ref<'a, [Annotation]>);
+
+impl Display for Tree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for node in self.0.iter() {
+            let Range { start, end } = node.range;
+            write!(f, "{:indent$}", "", indent = node.depth * 2)?;
+            match &node.value {
+                AnnotationValue::Unlabelled => write!(f, "<unlabelled>")?,
+                _ => write!(f, "{}", node.label)?,
+            }
+            match &node.value {
+                AnnotationValue::Group | AnnotationValue::Unlabelled => {}
+                AnnotationValue::Value(value) => write!(f, ": {value}")?,
+                AnnotationValue::TooShort => write!(f, ": <too short>")?,
+                AnnotationValue::Failed => write!(f, ": <failed>")?,
+            }
+            writeln!(f, " [{start}..{end}]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Read, alloc::string::ToString};
+
+    #[test]
+    fn records_failure() {
+        let mut src = Annotator::new(&[0x01, 0x02, 0x03][..]);
+        src.read_labelled::<u8>("a").unwrap();
+        src.read_labelled::<bool>("b").unwrap_err();
+        src.read_labelled::<u32>("c").unwrap_err();
+
+        assert_eq!(
+            &[
+                Annotation {
+                    label: "a".into(),
+                    depth: 0,
+                    range: 0..1,
+                    value: AnnotationValue::Value("1".into()),
+                },
+                Annotation {
+                    label: "b".into(),
+                    depth: 0,
+                    range: 1..2,
+                    value: AnnotationValue::Failed,
+                },
+                Annotation {
+                    label: "c".into(),
+                    depth: 0,
+                    range: 2..2,
+                    value: AnnotationValue::TooShort,
+                },
+            ],
+            &*src.annotations()
+        );
+    }
+
+    #[test]
+    fn nested_groups() {
+        let mut src = Annotator::new(&[1, 2, 3, 4][..]);
+        src.group("outer", |src| {
+            src.read_labelled::<u8>("first").unwrap();
+            src.group("inner", |src| {
+                src.read_labelled::<u8>("second").unwrap();
+                // unlabelled
+                Read::read::<u8>(src).unwrap();
+            });
+        });
+        src.read_labelled::<u8>("last").unwrap();
+
+        assert_eq!(
+            "outer [0..3]\n  first: 1 [0..1]\n  inner [1..3]\n    second: 2 [1..2]\n    \
+             <unlabelled> [2..3]\nlast: 4 [3..4]\n",
+            src.tree().to_string()
+        );
+        assert_eq!(4, src.position());
+    }
+
+    #[test]
+    fn merges_unlabelled() {
+        let mut src = Annotator::new(&[1, 2, 3, 4, 5, 6, 7, 8][..]);
+        Read::read::<u16>(&mut src).unwrap();
+        // a varint is read one byte at a time
+        Read::read::<crate::VarInt<u32>>(&mut src).unwrap();
+        src.read_labelled::<u8>("labelled").unwrap();
+        src.group("group", |src| Read::read::<u16>(src).unwrap());
+        src.advance(0);
+        Read::read::<u8>(&mut src).unwrap();
+
+        assert_eq!(
+            "<unlabelled> [0..3]\nlabelled: 4 [3..4]\ngroup [4..6]\n  <unlabelled> [4..6]\n\
+             <unlabelled> [6..7]\n",
+            src.tree().to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn nested_labels() {
+        use {
+            crate::{BufTooShort, Bytes, inspect::ReadLabelled},
+            core::convert::Infallible,
+        };
+
+        #[derive(Debug)]
+        struct Inner(u8);
+
+        impl Decode for Inner {
+            type Error = Infallible;
+
+            fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                Ok(Self(src.read_labelled("byte")?))
+            }
+        }
+
+        #[derive(Debug)]
+        struct Outer;
+
+        impl Decode for Outer {
+            type Error = Infallible;
+
+            fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                src.read_labelled::<Inner>("first")?;
+                // sections still read from the annotator
+                src.read_section::<Inner>(1).map_err(|_| BufTooShort)?;
+                // not recorded, since they aren't read from the annotator
+                let Inner(other) = Bytes::from_static(&[5]).read_labelled("other")?;
+                assert_eq!(5, other);
+                src.read::<u8>()?;
+                Ok(Self)
+            }
+        }
+
+        let mut src = Annotator::new(&[1, 2, 3, 4][..]);
+        src.read_labelled::<Outer>("outer").unwrap();
+        // not recorded, since no annotator is decoding a labelled value
+        (&[6][..]).read_labelled::<Inner>("unused").unwrap();
+        src.read_labelled::<Inner>("last").unwrap();
+
+        assert_eq!(
+            "outer: Outer [0..3]\n  first: Inner(1) [0..1]\n    byte: 1 [0..1]\n  \
+             byte: 2 [1..2]\nlast: Inner(4) [3..4]\n  byte: 4 [3..4]\n",
+            src.tree().to_string()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use {
+    crate::Buf,
+    core::fmt::{self, Display},
+};
+
+const PER_LINE: usize = 16;
+
+/// Formats the bytes of a [`Buf`] as a hexdump, in the style of `hexdump -C`.
+///
+/// Each line contains the offset of its first byte, up to 16 bytes formatted
+/// as hex, and the same bytes as ASCII, where non-printable bytes are shown as
+/// `.`.
+///
+/// The buffer is not consumed - formatting operates on a clone of `buf`, so
+/// for a [`Bytes`] or `&[u8]` this is cheap.
+///
+/// # Examples
+///
+/// ```
+/// # use octs::inspect::hexdump;
+/// let buf = &b"hello world\x00\x01\x02\xff\x80!"[..];
+/// assert_eq!(
+///     "00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 00 01 02 ff 80  |hello world.....|\n\
+///      00000010  21                                                |!|\n",
+///     hexdump(buf).to_string(),
+/// );
+/// ```
+///
+/// [`Bytes`]: crate::Bytes
+pub const fn hexdump<B: Buf + Clone>(buf: B) -> Hexdump<B> {
+    Hexdump { buf }
+}
+
+/// Hexdump of the bytes in a [`Buf`].
+///
+/// Use [`hexdump`] to create.
+#[derive(Debug, Clone)]
+pub struct Hexdump<B> {
+    buf: B,
+}
+
+impl<B: Buf + Clone> Display for Hexdump<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = self.buf.clone();
+        let mut offset = 0;
+        while buf.has_remaining() {
+            let mut line = [0u8; PER_LINE];
+            let len = buf.remaining().min(PER_LINE);
+            buf.copy_to_slice(&mut line[..len]);
+            let line = &line[..len];
+
+            write!(f, "{offset:08x} ")?;
+            for i in 0..PER_LINE {
+                if i % 8 == 0 {
+                    write!(f, " ")?;
+                }
+                match line.get(i) {
+                    Some(byte) => write!(f, "{byte:02x} ")?,
+                    None => write!(f, "   ")?,
+                }
+            }
+            write!(f, " |")?;
+            for &byte in line {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f, "|")?;
+
+            offset += len;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::Bytes, alloc::string::ToString};
+
+    #[test]
+    fn empty() {
+        assert_eq!("", hexdump(&[][..]).to_string());
+    }
+
+    #[test]
+    fn exact_line() {
+        assert_eq!(
+            "00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|\n",
+            hexdump(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15][..]).to_string()
+        );
+    }
+
+    #[test]
+    fn does_not_consume() {
+        let buf = Bytes::from_static(b"abcdef");
+        assert_eq!(
+            "00000000  61 62 63 64 65 66                                 |abcdef|\n",
+            hexdump(buf.clone()).to_string()
+        );
+        assert_eq!(6, buf.len());
+    }
+}
@@ -0,0 +1,83 @@
+use {
+    crate::{BufTooShortOr, Decode, Read},
+    core::fmt::Debug,
+};
+
+/// Allows [`Decode`] impls to label the values they are made of, so that an
+/// `Annotator` can break them down into nested nodes.
+///
+/// A [`Decode`] impl only sees an `impl Read`, so it can't tell if it is
+/// reading from an `Annotator`. Instead, while `Annotator::read_labelled`
+/// is decoding a value on this thread, any values read with
+/// [`ReadLabelled::read_labelled`] are recorded as children of that value's
+/// node. Otherwise, [`ReadLabelled::read_labelled`] is the same as
+/// [`Read::read`], so it is safe to use in any [`Decode`] impl.
+///
+/// Finding the current `Annotator` requires the `std` feature. Without it,
+/// [`ReadLabelled::read_labelled`] never records anything.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use octs::{
+///     BufTooShortOr, Decode, Read,
+///     inspect::{Annotator, ReadLabelled},
+///     prim::InvalidValue,
+/// };
+///
+/// #[derive(Debug)]
+/// struct Header {
+///     packet_id: u16,
+///     reliable: bool,
+/// }
+///
+/// impl Decode for Header {
+///     type Error = InvalidValue;
+///
+///     fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+///         Ok(Self {
+///             packet_id: src.read_labelled("packet_id")?,
+///             reliable: src.read_labelled("reliable")?,
+///         })
+///     }
+/// }
+///
+/// let mut src = Annotator::new(&[0x00, 0x05, 0x01][..]);
+/// src.read_labelled::<Header>("header").unwrap();
+///
+/// assert_eq!(
+///     "header: Header { packet_id: 5, reliable: true } [0..3]\n\
+///     \x20 packet_id: 5 [0..2]\n\
+///     \x20 reliable: true [2..3]\n",
+///     src.tree().to_string(),
+/// );
+/// # }
+/// ```
+pub trait ReadLabelled: Read {
+    /// Attempts to read the next `T` in the buffer, recording the bytes it
+    /// consumed under `label` if an `Annotator` is currently decoding a
+    /// labelled value on this thread.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are not enough bytes remaining in the buffer, or if a
+    /// value of `T` could not be read from the bytes in the buffer.
+    #[inline]
+    fn read_labelled<T: Decode + Debug>(
+        &mut self,
+        label: &str,
+    ) -> Result<T, BufTooShortOr<T::Error>>
+    where
+        Self: Sized,
+    {
+        #[cfg(feature = "std")]
+        if let Some(state) = super::annotate::current::get() {
+            return super::annotate::read_labelled(&state, self, label.into());
+        }
+        _ = label;
+        self.read()
+    }
+}
+
+impl<T: Read + ?Sized> ReadLabelled for T {}
@@ -0,0 +1,22 @@
+//! Utilities for inspecting encoded bytes when debugging.
+//!
+//! * [`hexdump`] formats any [`Buf`] as a classic hexdump, with offset, hex and
+//!   ASCII columns.
+//! * `Annotator` wraps a [`Buf`] and records which byte ranges were consumed by
+//!   the values you label while decoding, rendering them as a tree; requires
+//!   the `alloc` feature.
+//! * [`ReadLabelled`] lets [`Decode`] impls label the values they are made of,
+//!   so that `Annotator` can break them down into nested nodes; recording
+//!   these requires the `std` feature.
+//!
+//! [`Buf`]: crate::Buf
+//! [`Decode`]: crate::Decode
+
+#[cfg(feature = "alloc")]
+mod annotate;
+mod hexdump;
+mod label;
+
+#[cfg(feature = "alloc")]
+pub use annotate::*;
+pub use {hexdump::*, label::*};
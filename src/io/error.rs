@@ -0,0 +1,65 @@
+use {
+    crate::BufTooShortOr,
+    core::{error::Error, fmt::Display},
+    std::io,
+};
+
+/// Error when reading a value from an async stream.
+#[derive(Debug)]
+pub enum ReadError<E> {
+    /// Failed to read bytes from the stream, or the stream ended before the
+    /// value could be fully read.
+    Io(io::Error),
+    /// Failed to decode the value from the bytes read.
+    ///
+    /// This is [`BufTooShortOr::TooShort`] if the value could not be decoded
+    /// from [`FixedEncodeLenHint::MAX_ENCODE_LEN`] bytes.
+    ///
+    /// [`FixedEncodeLenHint::MAX_ENCODE_LEN`]: crate::FixedEncodeLenHint::MAX_ENCODE_LEN
+    Decode(BufTooShortOr<E>),
+}
+
+impl<E: Display> Display for ReadError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Decode(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ReadError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Decode(err) => Some(err),
+        }
+    }
+}
+
+/// Error when writing a value to an async stream.
+#[derive(Debug)]
+pub enum WriteError<E> {
+    /// Failed to write bytes to the stream.
+    Io(io::Error),
+    /// Failed to encode the value.
+    Encode(BufTooShortOr<E>),
+}
+
+impl<E: Display> Display for WriteError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Encode(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for WriteError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Encode(err) => Some(err),
+        }
+    }
+}
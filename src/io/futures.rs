@@ -0,0 +1,155 @@
+//! Extensions for [`futures-io`](https://docs.rs/futures-io)'s async I/O traits.
+//!
+//! See the [parent module](super) for details.
+
+use {
+    super::{PollRead, PollWrite, ReadError, WriteError},
+    crate::{Decode, Encode, FixedEncodeLenHint},
+    core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    },
+    futures_io::{AsyncRead, AsyncWrite},
+    std::io,
+};
+
+struct Reader<'a, R: ?Sized>(&'a mut R);
+
+impl<R: AsyncRead + Unpin + ?Sized> PollRead for Reader<'_, R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.0).poll_read(cx, buf)
+    }
+}
+
+struct Writer<'a, W: ?Sized>(&'a mut W);
+
+impl<W: AsyncWrite + Unpin + ?Sized> PollWrite for Writer<'_, W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut *self.0).poll_write(cx, buf)
+    }
+}
+
+/// Allows reading values from a [`futures-io`](https://docs.rs/futures-io)
+/// [`AsyncRead`].
+///
+/// Only types which implement [`FixedEncodeLenHint`] can be read, since the
+/// maximum length of the value must be known to avoid reading past it. Types
+/// with an unbounded length prefix, such as [`Bytes`] or [`Value`], can't be
+/// read this way - instead, read a length-prefixed frame from the stream
+/// yourself, and decode the value from that.
+///
+/// [`Bytes`]: crate::Bytes
+/// [`Value`]: crate::value::Value
+pub trait AsyncReadValue: AsyncRead + Unpin {
+    /// Reads the next `T` from this stream.
+    ///
+    /// This reads only the bytes which make up the value, using
+    /// [`FixedEncodeLenHint`] to size reads. See the
+    /// [parent module](super#reading) for details.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancel safe. If the future is dropped before it
+    /// completes, such as when it loses a `select!`, any bytes of the value
+    /// which were already read from the stream are lost, and the stream is
+    /// left part-way through the value with no error. Don't use this method as
+    /// a branch of a `select!` loop - instead, read values in a separate task
+    /// and send them over a channel.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the stream fails or ends before the value is fully read, or if
+    /// the value could not be decoded.
+    fn read_value<T>(
+        &mut self,
+    ) -> impl Future<Output = Result<T, ReadError<<T as Decode>::Error>>> + Send
+    where
+        Self: Send,
+        T: Decode + FixedEncodeLenHint;
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncReadValue for R {
+    fn read_value<T>(
+        &mut self,
+    ) -> impl Future<Output = Result<T, ReadError<<T as Decode>::Error>>> + Send
+    where
+        Self: Send,
+        T: Decode + FixedEncodeLenHint,
+    {
+        super::read_value(Reader(self))
+    }
+}
+
+/// Allows writing values to a [`futures-io`](https://docs.rs/futures-io)
+/// [`AsyncWrite`].
+pub trait AsyncWriteValue: AsyncWrite + Unpin {
+    /// Encodes `value` and writes it all to this stream.
+    ///
+    /// The stream is not flushed afterwards.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancel safe. If the future is dropped before it
+    /// completes, only part of the value may have been written to the stream,
+    /// and a peer reading from it will see a truncated value.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the value could not be encoded, or the stream fails.
+    fn write_value<T>(
+        &mut self,
+        value: &T,
+    ) -> impl Future<Output = Result<(), WriteError<T::Error>>> + Send
+    where
+        Self: Send,
+        T: Encode + Sync + ?Sized;
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> AsyncWriteValue for W {
+    fn write_value<T>(
+        &mut self,
+        value: &T,
+    ) -> impl Future<Output = Result<(), WriteError<T::Error>>> + Send
+    where
+        Self: Send,
+        T: Encode + Sync + ?Sized,
+    {
+        super::write_value(Writer(self), value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{VarInt, io::block_on},
+        alloc::vec::Vec,
+    };
+
+    #[test]
+    fn round_trip() {
+        let mut dst = Vec::new();
+        block_on(async {
+            dst.write_value(&VarInt(300u32)).await.unwrap();
+            dst.write_value(&true).await.unwrap();
+        });
+
+        let mut src = &dst[..];
+        block_on(async {
+            assert_eq!(300, src.read_value::<VarInt<u32>>().await.unwrap().0);
+            assert!(src.read_value::<bool>().await.unwrap());
+        });
+        assert_eq!(0, src.len());
+    }
+
+    #[test]
+    fn futures_are_send() {
+        fn assert_send<T: Send>(_: T) {}
+
+        let mut src = &[][..];
+        assert_send(src.read_value::<VarInt<u32>>());
+        let mut dst = Vec::new();
+        assert_send(dst.write_value(&VarInt(0u32)));
+    }
+}
@@ -0,0 +1,230 @@
+//! Allows reading and writing values directly from and to async streams.
+//!
+//! Each async runtime's I/O traits are supported behind a feature:
+//! * `tokio` - `tokio::AsyncReadValue` and `tokio::AsyncWriteValue`, for
+//!   [`tokio`](https://docs.rs/tokio)'s `AsyncRead` and `AsyncWrite`
+//! * `futures-io` - `futures::AsyncReadValue` and `futures::AsyncWriteValue`,
+//!   for [`futures-io`](https://docs.rs/futures-io)'s `AsyncRead` and
+//!   `AsyncWrite`
+//!
+//! # Reading
+//!
+//! `read_value` reads only as many bytes from the stream as it needs to decode
+//! the value, so the stream is left positioned right after it. This means
+//! that you can read values one after another without a separate framing
+//! layer.
+//!
+//! To do this without knowing the value's length up front, it first reads
+//! [`FixedEncodeLenHint::MIN_ENCODE_LEN`] bytes, then tries to decode the
+//! value. While the buffer is too short to decode from, it reads one more byte
+//! and tries again, up to [`FixedEncodeLenHint::MAX_ENCODE_LEN`] bytes. Values
+//! with a fixed length are read in a single go. Since this may issue many
+//! small reads on the stream, you should wrap unbuffered streams in a buffered
+//! reader.
+//!
+//! Only types which implement [`FixedEncodeLenHint`] can be read, so types
+//! with an unbounded length prefix, such as [`Bytes`] or [`Value`], are not
+//! supported.
+//!
+//! # Writing
+//!
+//! `write_value` encodes the value into an intermediate buffer, then writes
+//! all of it to the stream. The stream is not flushed.
+//!
+//! # Cancel safety
+//!
+//! Neither `read_value` nor `write_value` is cancel safe. If either future is
+//! dropped part-way, such as when it loses a `select!`, the bytes already
+//! read or written are lost, and the stream is left in the middle of a value
+//! with no error.
+//!
+//! [`Bytes`]: crate::Bytes
+//! [`Value`]: crate::value::Value
+
+mod error;
+#[cfg(feature = "futures-io")]
+pub mod futures;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+pub use error::*;
+use {
+    crate::{BufTooShortOr, BytesMut, Decode, Encode, FixedEncodeLenHint, Read, Write},
+    alloc::{vec, vec::Vec},
+    core::{
+        future::poll_fn,
+        task::{Context, Poll},
+    },
+    std::io,
+};
+
+/// Async stream which can be read from, abstracting over each runtime's
+/// `AsyncRead`.
+trait PollRead {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+}
+
+/// Async stream which can be written to, abstracting over each runtime's
+/// `AsyncWrite`.
+trait PollWrite {
+    fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>>;
+}
+
+async fn read_exact<R: PollRead + Send>(src: &mut R, mut buf: &mut [u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        match poll_fn(|cx| src.poll_read(cx, buf)).await {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => buf = &mut core::mem::take(&mut buf)[n..],
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+async fn write_all<W: PollWrite + Send>(dst: &mut W, mut buf: &[u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        match poll_fn(|cx| dst.poll_write(cx, buf)).await {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => buf = &buf[n..],
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+async fn read_value<T, R>(mut src: R) -> Result<T, ReadError<<T as Decode>::Error>>
+where
+    T: Decode + FixedEncodeLenHint,
+    R: PollRead + Send,
+{
+    let mut buf: Vec<u8> = vec![0; T::MIN_ENCODE_LEN];
+    read_exact(&mut src, &mut buf)
+        .await
+        .map_err(ReadError::Io)?;
+    loop {
+        match (&buf[..]).read::<T>() {
+            Ok(value) => return Ok(value),
+            Err(BufTooShortOr::TooShort) if buf.len() < T::MAX_ENCODE_LEN => {}
+            Err(err) => return Err(ReadError::Decode(err)),
+        }
+        // we can't read ahead, since those bytes may belong to the next value
+        let len = buf.len();
+        buf.push(0);
+        read_exact(&mut src, &mut buf[len..])
+            .await
+            .map_err(ReadError::Io)?;
+    }
+}
+
+async fn write_value<T, W>(mut dst: W, value: &T) -> Result<(), WriteError<T::Error>>
+where
+    T: Encode + Sync + ?Sized,
+    W: PollWrite + Send,
+{
+    let mut buf = BytesMut::new();
+    buf.write(value).map_err(WriteError::Encode)?;
+    write_all(&mut dst, &buf).await.map_err(WriteError::Io)
+}
+
+#[cfg(test)]
+fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use {
+        core::{pin::pin, task::Waker},
+        std::{sync::Arc, task::Wake},
+    };
+
+    struct NoopWake;
+
+    impl Wake for NoopWake {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(NoopWake));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::VarInt};
+
+    /// Stream which yields at most one byte per read, and is pending on every
+    /// other poll.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        pending: bool,
+    }
+
+    impl PollRead for &mut Trickle<'_> {
+        fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let Some((&first, rest)) = self.bytes.split_first() else {
+                return Poll::Ready(Ok(0));
+            };
+            buf[0] = first;
+            self.bytes = rest;
+            Poll::Ready(Ok(1))
+        }
+    }
+
+    fn trickle(bytes: &[u8]) -> Trickle<'_> {
+        Trickle {
+            bytes,
+            pending: false,
+        }
+    }
+
+    #[test]
+    fn read_values_in_sequence() {
+        let mut buf = BytesMut::new();
+        buf.write(VarInt(300u32)).unwrap();
+        buf.write(0x1234_5678u32).unwrap();
+        buf.write(VarInt(1u64)).unwrap();
+
+        let mut src = trickle(&buf);
+        assert_eq!(
+            300,
+            block_on(read_value::<VarInt<u32>, _>(&mut src)).unwrap().0
+        );
+        assert_eq!(
+            0x1234_5678,
+            block_on(read_value::<u32, _>(&mut src)).unwrap()
+        );
+        assert_eq!(
+            1,
+            block_on(read_value::<VarInt<u64>, _>(&mut src)).unwrap().0
+        );
+        assert_eq!(0, src.bytes.len());
+    }
+
+    #[test]
+    fn read_eof() {
+        let mut src = trickle(&[0x80, 0x80]);
+        assert!(matches!(
+            block_on(read_value::<VarInt<u32>, _>(&mut src)),
+            Err(ReadError::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn read_invalid() {
+        let mut src = trickle(&[0xff; 16]);
+        assert!(matches!(
+            block_on(read_value::<VarInt<u32>, _>(&mut src)),
+            Err(ReadError::Decode(BufTooShortOr::Or(_)))
+        ));
+        // only read up to the max length of the value
+        assert_eq!(16 - VarInt::<u32>::MAX_ENCODE_LEN, src.bytes.len());
+    }
+}
@@ -2,15 +2,43 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 
+// `bytes` always depends on `alloc`, so we can always link to it too; the
+// `alloc` feature only controls our own APIs built on `alloc`'s collections
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+// only used in benchmarks and tests
+#[cfg(test)]
+use {criterion as _, serde as _};
+
 pub use bytes::{self, Buf, BufMut, Bytes, BytesMut};
 
+mod counting;
 mod error;
 mod read;
+mod stack;
 mod varint;
 mod write;
 
+pub mod checksum;
 pub mod chunks;
+#[cfg(feature = "alloc")]
+pub mod compress;
+pub mod inspect;
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub mod io;
+pub mod net;
 pub mod prim;
+pub mod quant;
+#[cfg(feature = "alloc")]
+pub mod registry;
+#[cfg(feature = "alloc")]
+pub mod schema;
 pub mod test;
+pub mod time;
+#[cfg(feature = "alloc")]
+pub mod value;
+pub mod version;
 
-pub use {error::*, read::*, varint::*, write::*};
+pub use {counting::*, error::*, read::*, stack::*, varint::*, write::*};
@@ -0,0 +1,290 @@
+//! Implementations of [`Decode`] and [`Encode`] for network address types
+//! from [`core::net`].
+//!
+//! These types have been available in `core` since Rust 1.77, below this
+//! crate's minimum supported Rust version, so they are always implemented.
+//!
+//! # Wire format
+//!
+//! * [`Ipv4Addr`] - 4 octets
+//! * [`Ipv6Addr`] - 16 octets
+//! * [`IpAddr`] - tag as a [`u8`] (`0` for V4, `1` for V6), then the address
+//! * [`SocketAddrV4`] - address, then port as a big-endian [`u16`]
+//! * [`SocketAddrV6`] - address, then port as a big-endian [`u16`]; the flow
+//!   info and scope ID are not encoded, and are always `0` when decoded
+//! * [`SocketAddr`] - tag as a [`u8`] (`0` for V4, `1` for V6), then the
+//!   socket address
+//!
+//! Decoding an [`IpAddr`] or [`SocketAddr`] with an unknown tag fails with
+//! [`InvalidValue`].
+
+use {
+    crate::{
+        BufTooShortOr, Decode, Encode, EncodeLen, FixedEncodeLen, FixedEncodeLenHint, Read, Write,
+        prim::InvalidValue,
+    },
+    core::{
+        convert::Infallible,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    },
+};
+
+const TAG_V4: u8 = 0;
+const TAG_V6: u8 = 1;
+
+impl FixedEncodeLen for Ipv4Addr {
+    const ENCODE_LEN: usize = 4;
+}
+
+impl Decode for Ipv4Addr {
+    type Error = Infallible;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        Ok(Self::from(src.read_exact::<4>()?))
+    }
+}
+
+impl Encode for Ipv4Addr {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write_from(&self.octets()[..])?;
+        Ok(())
+    }
+}
+
+impl FixedEncodeLen for Ipv6Addr {
+    const ENCODE_LEN: usize = 16;
+}
+
+impl Decode for Ipv6Addr {
+    type Error = Infallible;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        Ok(Self::from(src.read_exact::<16>()?))
+    }
+}
+
+impl Encode for Ipv6Addr {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write_from(&self.octets()[..])?;
+        Ok(())
+    }
+}
+
+impl EncodeLen for IpAddr {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        1 + match self {
+            Self::V4(addr) => addr.encode_len(),
+            Self::V6(addr) => addr.encode_len(),
+        }
+    }
+}
+
+impl FixedEncodeLenHint for IpAddr {
+    const MIN_ENCODE_LEN: usize = 1 + Ipv4Addr::ENCODE_LEN;
+
+    const MAX_ENCODE_LEN: usize = 1 + Ipv6Addr::ENCODE_LEN;
+}
+
+impl Decode for IpAddr {
+    type Error = InvalidValue;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        match src.read::<u8>()? {
+            TAG_V4 => Ok(Self::V4(src.read()?)),
+            TAG_V6 => Ok(Self::V6(src.read()?)),
+            _ => Err(InvalidValue(()).into()),
+        }
+    }
+}
+
+impl Encode for IpAddr {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        match self {
+            Self::V4(addr) => {
+                dst.write(TAG_V4)?;
+                dst.write(addr)
+            }
+            Self::V6(addr) => {
+                dst.write(TAG_V6)?;
+                dst.write(addr)
+            }
+        }
+    }
+}
+
+impl FixedEncodeLen for SocketAddrV4 {
+    const ENCODE_LEN: usize = Ipv4Addr::ENCODE_LEN + u16::ENCODE_LEN;
+}
+
+impl Decode for SocketAddrV4 {
+    type Error = Infallible;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        Ok(Self::new(src.read()?, src.read()?))
+    }
+}
+
+impl Encode for SocketAddrV4 {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write(self.ip())?;
+        dst.write(self.port())
+    }
+}
+
+impl FixedEncodeLen for SocketAddrV6 {
+    const ENCODE_LEN: usize = Ipv6Addr::ENCODE_LEN + u16::ENCODE_LEN;
+}
+
+impl Decode for SocketAddrV6 {
+    type Error = Infallible;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        Ok(Self::new(src.read()?, src.read()?, 0, 0))
+    }
+}
+
+impl Encode for SocketAddrV6 {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write(self.ip())?;
+        dst.write(self.port())
+    }
+}
+
+impl EncodeLen for SocketAddr {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        1 + match self {
+            Self::V4(addr) => addr.encode_len(),
+            Self::V6(addr) => addr.encode_len(),
+        }
+    }
+}
+
+impl FixedEncodeLenHint for SocketAddr {
+    const MIN_ENCODE_LEN: usize = 1 + SocketAddrV4::ENCODE_LEN;
+
+    const MAX_ENCODE_LEN: usize = 1 + SocketAddrV6::ENCODE_LEN;
+}
+
+impl Decode for SocketAddr {
+    type Error = InvalidValue;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        match src.read::<u8>()? {
+            TAG_V4 => Ok(Self::V4(src.read()?)),
+            TAG_V6 => Ok(Self::V6(src.read()?)),
+            _ => Err(InvalidValue(()).into()),
+        }
+    }
+}
+
+impl Encode for SocketAddr {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        match self {
+            Self::V4(addr) => {
+                dst.write(TAG_V4)?;
+                dst.write(addr)
+            }
+            Self::V6(addr) => {
+                dst.write(TAG_V6)?;
+                dst.write(addr)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{BytesMut, test::*},
+    };
+
+    const V4: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 2);
+    const V6: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+    #[test]
+    fn round_trip_ip() {
+        hint_round_trip(&V4);
+        hint_round_trip(&V6);
+        hint_round_trip(&IpAddr::V4(V4));
+        hint_round_trip(&IpAddr::V6(V6));
+        hint_round_trip(&IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+    }
+
+    #[test]
+    fn round_trip_socket() {
+        let v4 = SocketAddrV4::new(V4, 8080);
+        let v6 = SocketAddrV6::new(V6, 443, 0, 0);
+        hint_round_trip(&v4);
+        hint_round_trip(&v6);
+        hint_round_trip(&SocketAddr::V4(v4));
+        hint_round_trip(&SocketAddr::V6(v6));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(IpAddr::V4(V4), "00 c0 a8 01 02");
+        crate::assert_encodes!(
+            SocketAddr::V4(SocketAddrV4::new(V4, 0x1f90)),
+            "00 c0 a8 01 02 1f 90"
+        );
+        crate::assert_encodes!(
+            SocketAddr::V6(SocketAddrV6::new(V6, 443, 0, 0)),
+            "01 20 01 0d b8 00 00 00 00 00 00 00 00 00 00 00 01 01 bb"
+        );
+    }
+
+    #[test]
+    fn v6_flow_info_and_scope_id_not_encoded() {
+        let mut buf = BytesMut::new();
+        buf.write(SocketAddrV6::new(V6, 443, 0x12345, 7)).unwrap();
+        assert_eq!(SocketAddrV6::ENCODE_LEN, buf.len());
+        assert_eq!(
+            SocketAddrV6::new(V6, 443, 0, 0),
+            buf.freeze().read::<SocketAddrV6>().unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_invalid_tag() {
+        (&[2, 192, 168, 1, 2][..]).read::<IpAddr>().unwrap_err();
+        (&[0xff, 192, 168, 1, 2, 0, 80][..])
+            .read::<SocketAddr>()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn decode_malformed() {
+        let addr = SocketAddr::V6(SocketAddrV6::new(V6, 443, 0, 0));
+        truncated(&IpAddr::V6(V6));
+        truncated(&addr);
+        byte_flips(&addr);
+    }
+}
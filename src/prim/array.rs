@@ -0,0 +1,139 @@
+/// Implements [`Encode`], [`Decode`] and [`FixedEncodeLen`] for a newtype
+/// around a `[u8; N]`, such as an identifier or hash.
+///
+/// With the `alloc` feature, this also implements `Schema`, describing the type
+/// as an array of `N` bytes.
+///
+/// The type must be a tuple struct whose only field is the byte array. It is
+/// encoded as exactly those `N` bytes, and decoded using
+/// [`Read::read_exact`], which copies the bytes directly out of the buffer
+/// when they are contiguous, rather than decoding byte-by-byte.
+///
+/// # Examples
+///
+/// ```
+/// use octs::{BytesMut, Read, Write};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct SessionHash([u8; 32]);
+///
+/// octs::impl_byte_array!(SessionHash, 32);
+///
+/// let hash = SessionHash([0xab; 32]);
+/// let mut buf = BytesMut::new();
+/// buf.write(&hash).unwrap();
+/// assert_eq!(32, buf.len());
+/// assert_eq!(hash, buf.freeze().read::<SessionHash>().unwrap());
+/// ```
+///
+/// [`Encode`]: crate::Encode
+/// [`Decode`]: crate::Decode
+/// [`FixedEncodeLen`]: crate::FixedEncodeLen
+/// [`Read::read_exact`]: crate::Read::read_exact
+#[macro_export]
+macro_rules! impl_byte_array {
+    ($ty:ty, $len:expr) => {
+        impl $crate::FixedEncodeLen for $ty {
+            const ENCODE_LEN: usize = $len;
+        }
+
+        impl $crate::Decode for $ty {
+            type Error = ::core::convert::Infallible;
+
+            #[inline]
+            fn decode(
+                mut src: impl $crate::Read,
+            ) -> ::core::result::Result<Self, $crate::BufTooShortOr<Self::Error>> {
+                ::core::result::Result::Ok(Self($crate::Read::read_exact::<{ $len }>(&mut src)?))
+            }
+        }
+
+        impl $crate::Encode for $ty {
+            type Error = ::core::convert::Infallible;
+
+            #[inline]
+            fn encode(
+                &self,
+                mut dst: impl $crate::Write,
+            ) -> ::core::result::Result<(), $crate::BufTooShortOr<Self::Error>> {
+                $crate::Write::write_from(&mut dst, &self.0[..])?;
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        $crate::__impl_byte_array_schema!($ty, $len);
+    };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! __impl_byte_array_schema {
+    ($ty:ty, $len:expr) => {
+        impl $crate::schema::Schema for $ty {
+            fn schema() -> $crate::schema::SchemaType {
+                $crate::schema::__byte_array($len)
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "alloc"))]
+#[macro_export]
+macro_rules! __impl_byte_array_schema {
+    ($ty:ty, $len:expr) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Hash([u8; 32]);
+
+    crate::impl_byte_array!(Hash, 32);
+
+    #[derive(Debug, PartialEq)]
+    struct Tag([u8; 4]);
+
+    crate::impl_byte_array!(Tag, 4);
+
+    #[test]
+    fn round_trip() {
+        let mut hash = [0u8; 32];
+        for (i, b) in hash.iter_mut().enumerate() {
+            *b = u8::try_from(i).unwrap();
+        }
+        hint_round_trip(&Hash(hash));
+        hint_round_trip(&Tag(*b"octs"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(Tag(*b"octs"), "6f 63 74 73");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn schema() {
+        use crate::schema::{Schema, SchemaType};
+
+        assert_eq!(
+            SchemaType::Array {
+                item: alloc::boxed::Box::new(SchemaType::Int {
+                    signed: false,
+                    bytes: 1
+                }),
+                len: 4,
+            },
+            Tag::schema()
+        );
+    }
+
+    #[test]
+    fn decode_malformed() {
+        truncated(&Hash([0xff; 32]));
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use {
+    crate::{
+        BufTooShortOr, Bytes, BytesMut, Decode, Encode, EncodeLen, Read, VarInt, VarIntTooLarge,
+        Write,
+    },
+    core::convert::Infallible,
+};
+
+// Byte sequences are encoded as:
+// * length as a `VarInt<usize>`
+// * that many bytes
+
+#[inline]
+fn encoded_len(len: usize) -> usize {
+    VarInt(len).encode_len() + len
+}
+
+#[inline]
+fn encode_bytes(bytes: &[u8], mut dst: impl Write) -> Result<(), BufTooShortOr<Infallible>> {
+    if dst.remaining_mut() < encoded_len(bytes.len()) {
+        return Err(BufTooShortOr::TooShort);
+    }
+    dst.write(VarInt(bytes.len()))?;
+    dst.write_from(bytes)?;
+    Ok(())
+}
+
+#[inline]
+fn decode_bytes(mut src: impl Read) -> Result<Bytes, BufTooShortOr<VarIntTooLarge>> {
+    let VarInt(len) = src.read::<VarInt<usize>>()?;
+    Ok(src.read_next(len)?)
+}
+
+impl EncodeLen for [u8] {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        encoded_len(self.len())
+    }
+}
+
+impl Encode for [u8] {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        encode_bytes(self, dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodeLen for Vec<u8> {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        encoded_len(self.len())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Encode for Vec<u8> {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        encode_bytes(self, dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Decode for Vec<u8> {
+    type Error = VarIntTooLarge;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let VarInt(len) = src.read::<VarInt<usize>>()?;
+        if src.remaining() < len {
+            return Err(BufTooShortOr::TooShort);
+        }
+        let mut bytes = alloc::vec![0; len];
+        src.copy_to_slice(&mut bytes);
+        Ok(bytes)
+    }
+}
+
+impl EncodeLen for Bytes {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        encoded_len(self.len())
+    }
+}
+
+impl Encode for Bytes {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        encode_bytes(self, dst)
+    }
+}
+
+/// If `src` is itself a [`Bytes`], this does not copy - the returned value
+/// shares the same underlying allocation as `src`.
+impl Decode for Bytes {
+    type Error = VarIntTooLarge;
+
+    #[inline]
+    fn decode(src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        decode_bytes(src)
+    }
+}
+
+impl EncodeLen for BytesMut {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        encoded_len(self.len())
+    }
+}
+
+impl Encode for BytesMut {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        encode_bytes(self, dst)
+    }
+}
+
+impl Decode for BytesMut {
+    type Error = VarIntTooLarge;
+
+    #[inline]
+    fn decode(src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let bytes = decode_bytes(src)?;
+        Ok(Self::from(&bytes[..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    #[test]
+    fn round_trip_all() {
+        round_trip(&Bytes::new());
+        round_trip(&Bytes::from_static(b"hello world"));
+        round_trip(&Bytes::from(alloc::vec![0xab; 300]));
+        round_trip(&BytesMut::from(&b"hello world"[..]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trip_vec() {
+        round_trip(&alloc::vec![1u8, 2, 3]);
+        round_trip(&Vec::<u8>::new());
+    }
+
+    #[test]
+    fn encode_slice() {
+        let mut buf = BytesMut::new();
+        buf.write(&b"abc"[..]).unwrap();
+        assert_eq!(b"abc"[..].encode_len(), buf.len());
+        assert_eq!(&b"abc"[..], buf.freeze().read::<Bytes>().unwrap());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(Bytes::new(), "00");
+        crate::assert_encodes!(Bytes::from_static(b"octs"), "04 6f 63 74 73");
+    }
+
+    #[test]
+    fn encode_too_short() {
+        let mut buf = [0u8; 4];
+        let mut dst = &mut buf[..];
+        assert!(matches!(
+            dst.write(Bytes::from_static(b"octs")),
+            Err(BufTooShortOr::TooShort)
+        ));
+        assert_eq!(4, dst.len());
+    }
+
+    #[test]
+    fn decode_zero_copy() {
+        let mut buf = BytesMut::new();
+        buf.write(Bytes::from_static(b"zero copy")).unwrap();
+        let src = buf.freeze();
+
+        let bytes = src.clone().read::<Bytes>().unwrap();
+        assert_eq!(&b"zero copy"[..], bytes);
+        assert_eq!(src[1..].as_ptr(), bytes.as_ptr());
+    }
+
+    #[test]
+    fn decode_malformed() {
+        let value = Bytes::from_static(b"hello world");
+        truncated(&value);
+        byte_flips(&value);
+
+        // length is larger than what's left
+        (&[0xff, 0xff, 0x03, 0x00][..]).read::<Bytes>().unwrap_err();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decode_malformed_vec() {
+        truncated(&b"hello world".to_vec());
+        (&[0xff, 0xff, 0x03, 0x00][..])
+            .read::<Vec<u8>>()
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    super::InvalidValue,
+    crate::{
+        BufTooShortOr, Decode, Encode, EncodeLen, FixedEncodeLen, FixedEncodeLenHint, Read, Write,
+    },
+    core::convert::Infallible,
+};
+
+impl FixedEncodeLen for char {
+    const ENCODE_LEN: usize = 4;
+}
+
+impl Decode for char {
+    type Error = InvalidValue;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let scalar = src.read::<u32>()?;
+        Self::from_u32(scalar).ok_or_else(|| InvalidValue(()).into())
+    }
+}
+
+impl Encode for char {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write(u32::from(*self))
+    }
+}
+
+/// [`char`] which is encoded as its UTF-8 representation, taking between 1 and
+/// 4 bytes.
+///
+/// A raw [`char`] is always encoded as its 4-byte scalar value. Use this type
+/// instead when most of your characters are ASCII.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Utf8Char(pub char);
+
+impl From<char> for Utf8Char {
+    fn from(value: char) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Utf8Char> for char {
+    fn from(value: Utf8Char) -> Self {
+        value.0
+    }
+}
+
+impl EncodeLen for Utf8Char {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.0.len_utf8()
+    }
+}
+
+impl FixedEncodeLenHint for Utf8Char {
+    const MIN_ENCODE_LEN: usize = 1;
+
+    const MAX_ENCODE_LEN: usize = 4;
+}
+
+impl Decode for Utf8Char {
+    type Error = InvalidValue;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let mut bytes = [0u8; 4];
+        bytes[0] = src.read::<u8>()?;
+        let len = match bytes[0] {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(InvalidValue(()).into()),
+        };
+        for byte in &mut bytes[1..len] {
+            *byte = src.read::<u8>()?;
+        }
+
+        // rejects overlong encodings, surrogates, and values above U+10FFFF
+        core::str::from_utf8(&bytes[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .map(Self)
+            .ok_or_else(|| InvalidValue(()).into())
+    }
+}
+
+impl Encode for Utf8Char {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        let mut bytes = [0u8; 4];
+        dst.write_from(self.0.encode_utf8(&mut bytes).as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    const CHARS: [char; 8] = [
+        '\0',
+        'a',
+        '\x7f',
+        '\u{80}',
+        'ß',
+        '\u{ffff}',
+        '🦀',
+        char::MAX,
+    ];
+
+    #[test]
+    fn round_trip() {
+        for c in CHARS {
+            hint_round_trip(&c);
+            hint_round_trip(&Utf8Char(c));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!('a', "00000061");
+        crate::assert_encodes!('🦀', "0001f980");
+        crate::assert_encodes!(Utf8Char('a'), "61");
+        crate::assert_encodes!(Utf8Char('ß'), "c3 9f");
+        crate::assert_encodes!(Utf8Char('🦀'), "f0 9f a6 80");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        // surrogate
+        (&[0x00, 0x00, 0xd8, 0x00][..]).read::<char>().unwrap_err();
+        // out of range
+        (&[0x00, 0x11, 0x00, 0x00][..]).read::<char>().unwrap_err();
+
+        // continuation byte as the first byte
+        (&[0x80][..]).read::<Utf8Char>().unwrap_err();
+        // invalid first byte
+        (&[0xff][..]).read::<Utf8Char>().unwrap_err();
+        // surrogate
+        (&[0xed, 0xa0, 0x80][..]).read::<Utf8Char>().unwrap_err();
+        // overlong encoding of '/'
+        (&[0xc0, 0xaf][..]).read::<Utf8Char>().unwrap_err();
+        // out of range
+        (&[0xf4, 0x90, 0x80, 0x80][..])
+            .read::<Utf8Char>()
+            .unwrap_err();
+        // missing continuation
+        (&[0xc3, 0x41][..]).read::<Utf8Char>().unwrap_err();
+    }
+
+    #[test]
+    fn decode_malformed() {
+        for c in CHARS {
+            truncated(&c);
+            truncated(&Utf8Char(c));
+            byte_flips(&Utf8Char(c));
+        }
+    }
+}
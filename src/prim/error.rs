@@ -10,6 +10,21 @@ use {
 /// * [`bool`]: read a [`u8`] which was not `0x0` or `0x1`, the two valid values
 ///   which can represent a boolean.
 /// * `NonZero*`: read a value of `0`, which, unsurprisingly, is invalid.
+/// * [`char`]: read a [`u32`] which is a surrogate, or is above `U+10FFFF`.
+/// * [`Utf8Char`]: read bytes which are not a valid UTF-8 encoding of a single
+///   [`char`].
+/// * [`IpAddr`], [`SocketAddr`]: read a tag which was not `0` (V4) or `1`
+///   (V6).
+/// * [`Duration`]: read a number of nanoseconds which was not less than one
+///   second.
+/// * [`Quantized`]: read an integer with bits set above the type's `BITS`.
+/// * `Value`: read an unknown tag, or a string which was not valid UTF-8.
+///
+/// [`Utf8Char`]: crate::prim::Utf8Char
+/// [`IpAddr`]: core::net::IpAddr
+/// [`SocketAddr`]: core::net::SocketAddr
+/// [`Duration`]: core::time::Duration
+/// [`Quantized`]: crate::quant::Quantized
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct InvalidValue(pub(crate) ());
 
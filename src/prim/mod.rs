@@ -3,10 +3,15 @@
 //! [`Decode`]: crate::Decode
 //! [`Encode`]: crate::Encode
 
+mod array;
 mod bool;
+mod buf;
+mod char;
 mod error;
 mod nonzero;
 mod num;
+#[cfg(feature = "uuid")]
+mod uuid;
 mod zero_sized;
 
-pub use error::*;
+pub use {char::*, error::*, num::*};
@@ -3,8 +3,59 @@ use {
     core::{convert::Infallible, mem::size_of},
 };
 
+/// Numeric primitive which can be read and written in bulk, using
+/// [`Read::read_into`] and [`Write::write_slice`].
+///
+/// This trait is sealed, and implemented for all integer and floating-point
+/// types which implement [`Encode`] and [`Decode`].
+pub trait Number:
+    Copy + Default + FixedEncodeLen + Encode<Error = Infallible> + Decode<Error = Infallible> + Sealed
+{
+    /// Encodes each value in `src` into consecutive [`FixedEncodeLen::ENCODE_LEN`]
+    /// sized blocks of `dst`.
+    ///
+    /// `dst` must be exactly `src.len() * ENCODE_LEN` bytes long.
+    #[doc(hidden)]
+    fn encode_slice(src: &[Self], dst: &mut [u8]);
+
+    /// Decodes each value in `dst` from consecutive [`FixedEncodeLen::ENCODE_LEN`]
+    /// sized blocks of `src`.
+    ///
+    /// `src` must be exactly `dst.len() * ENCODE_LEN` bytes long.
+    #[doc(hidden)]
+    fn decode_slice(src: &[u8], dst: &mut [Self]);
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+use sealed::Sealed;
+
 macro_rules! impl_for {
     ($ty:ty) => {
+        impl Sealed for $ty {}
+
+        impl Number for $ty {
+            #[inline]
+            fn encode_slice(src: &[Self], dst: &mut [u8]) {
+                debug_assert_eq!(src.len() * size_of::<$ty>(), dst.len());
+                for (value, bytes) in src.iter().zip(dst.chunks_exact_mut(size_of::<$ty>())) {
+                    bytes.copy_from_slice(&value.to_be_bytes());
+                }
+            }
+
+            #[inline]
+            fn decode_slice(src: &[u8], dst: &mut [Self]) {
+                debug_assert_eq!(dst.len() * size_of::<$ty>(), src.len());
+                for (value, bytes) in dst.iter_mut().zip(src.chunks_exact(size_of::<$ty>())) {
+                    let mut array = [0; size_of::<$ty>()];
+                    array.copy_from_slice(bytes);
+                    *value = <$ty>::from_be_bytes(array);
+                }
+            }
+        }
+
         impl FixedEncodeLen for $ty {
             const ENCODE_LEN: usize = size_of::<$ty>();
         }
@@ -47,10 +98,19 @@ impl_for!(i128);
 
 impl_for!(f32);
 impl_for!(f64);
+#[cfg(feature = "half")]
+impl_for!(half::f16);
+#[cfg(feature = "half")]
+impl_for!(half::bf16);
 
 #[cfg(test)]
 mod tests {
-    use crate::test::*;
+    use {
+        super::*,
+        crate::{Buf, BytesMut, test::*},
+        alloc::{vec, vec::Vec},
+        core::fmt::Debug,
+    };
 
     macro_rules! round_trip {
         ($ty:ty) => {
@@ -63,6 +123,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::cast_precision_loss)] // casting small integer literals
     fn round_trip() {
         round_trip!(usize);
         round_trip!(isize);
@@ -83,4 +144,72 @@ mod tests {
         round_trip!(f32);
         round_trip!(f64);
     }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn bulk_round_trip() {
+        fn check<T: Number + Debug + PartialEq>(values: &[T]) {
+            let mut buf = BytesMut::new();
+            buf.write_slice(values).unwrap();
+            assert_eq!(values.len() * T::ENCODE_LEN, buf.len());
+
+            // must match encoding each value individually
+            let mut each = BytesMut::new();
+            for value in values {
+                each.write(value).unwrap();
+            }
+            assert_eq!(each, buf);
+
+            let buf = buf.freeze();
+            let mut actual = vec![T::default(); values.len()];
+            buf.clone().read_into(&mut actual).unwrap();
+            assert_eq!(values, actual);
+
+            // split into many chunks
+            let (front, back) = buf.split_at(buf.len() / 3);
+            let mut chained = front.chain(back);
+            let mut actual = vec![T::default(); values.len()];
+            chained.read_into(&mut actual).unwrap();
+            assert_eq!(values, actual);
+            assert!(!chained.has_remaining());
+        }
+
+        check::<u8>(&(0..=255).collect::<Vec<_>>());
+        check::<u16>(&(0..1000).map(|i| i * 65).collect::<Vec<_>>());
+        check::<i32>(&(-500..500).map(|i| i * 1_000_003).collect::<Vec<_>>());
+        check::<u64>(&(0..1000).map(|i| i << 40 | i).collect::<Vec<_>>());
+        check::<f32>(&(0..1000).map(|i| i as f32 * 0.25).collect::<Vec<_>>());
+        check::<f64>(&[]);
+    }
+
+    #[test]
+    fn bulk_too_short() {
+        let mut buf = [0u8; 7];
+        let mut dst = &mut buf[..];
+        dst.write_slice(&[1u32, 2]).unwrap_err();
+        // nothing was written
+        assert_eq!(7, dst.len());
+
+        let mut src = &[0u8; 7][..];
+        let mut values = [0u32; 2];
+        src.read_into(&mut values).unwrap_err();
+        // nothing was read
+        assert_eq!(7, src.len());
+    }
+
+    #[cfg(feature = "half")]
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trip_half() {
+        use half::{bf16, f16};
+
+        for v in [f16::MIN, f16::ZERO, f16::ONE, f16::MAX, f16::INFINITY] {
+            hint_round_trip(&v);
+        }
+        for v in [bf16::MIN, bf16::ZERO, bf16::ONE, bf16::MAX, bf16::INFINITY] {
+            hint_round_trip(&v);
+        }
+        crate::assert_encodes!(f16::ONE, "3c00");
+        crate::assert_encodes!(bf16::ONE, "3f80");
+    }
 }
@@ -0,0 +1,54 @@
+use {
+    crate::{BufTooShortOr, Decode, Encode, FixedEncodeLen, Read, Write},
+    core::convert::Infallible,
+    uuid::Uuid,
+};
+
+impl FixedEncodeLen for Uuid {
+    const ENCODE_LEN: usize = 16;
+}
+
+impl Decode for Uuid {
+    type Error = Infallible;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        Ok(Self::from_bytes(src.read_exact()?))
+    }
+}
+
+impl Encode for Uuid {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write_from(&self.as_bytes()[..])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    #[test]
+    fn round_trip() {
+        hint_round_trip(&Uuid::nil());
+        hint_round_trip(&Uuid::max());
+        hint_round_trip(&Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(
+            Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+            "67 e5 50 44 10 b1 42 6f 92 47 bb 68 0e 5f e0 c8"
+        );
+    }
+
+    #[test]
+    fn decode_malformed() {
+        truncated(&Uuid::max());
+    }
+}
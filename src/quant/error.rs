@@ -0,0 +1,22 @@
+use {
+    crate::BufError,
+    core::{error::Error, fmt::Display},
+};
+
+/// Attempted to encode a [`Quantized`] or [`Fixed`] value, but the value was
+/// outside of the range that the type can represent, or was NaN.
+///
+/// [`Quantized`]: crate::quant::Quantized
+/// [`Fixed`]: crate::quant::Fixed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutOfRange;
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "value out of range")
+    }
+}
+
+impl BufError for OutOfRange {}
+
+impl Error for OutOfRange {}
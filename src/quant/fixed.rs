@@ -0,0 +1,163 @@
+use {
+    super::{Float, OutOfRange, round},
+    crate::{BufTooShort, BufTooShortOr, Decode, Encode, FixedEncodeLen, Read, Write},
+    core::{convert::Infallible, marker::PhantomData, mem::size_of},
+};
+
+/// Floating-point value encoded as a fixed-point integer of type `I`, with
+/// `FRAC` fractional bits.
+///
+/// The value is multiplied by `2^FRAC` and rounded to the nearest integer on
+/// encode, so after a round trip, the value will be within `2^-(FRAC + 1)` of
+/// the original. The integer is encoded in the same way as `I` itself.
+///
+/// `FRAC` must be less than the number of bits in `I`. This is checked at
+/// compile time when encoding or decoding.
+///
+/// # Errors
+///
+/// Encoding a value which doesn't fit into `I` after scaling, or NaN, fails
+/// with [`OutOfRange`].
+///
+/// # Examples
+///
+/// ```
+/// # use octs::{BytesMut, Read, Write, quant::Fixed};
+/// // a velocity in the range -128..128, to within 1/512, in 2 bytes
+/// type Velocity = Fixed<f32, i16, 8>;
+///
+/// let mut buf = BytesMut::new();
+/// buf.write(Velocity::new(-3.25)).unwrap();
+/// assert_eq!(2, buf.len());
+///
+/// let velocity = buf.freeze().read::<Velocity>().unwrap();
+/// assert_eq!(-3.25, velocity.0);
+///
+/// // out of range
+/// BytesMut::new().write(Velocity::new(200.0)).unwrap_err();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[allow(clippy::derive_partial_eq_without_eq)] // `F` is a float
+pub struct Fixed<F, I, const FRAC: u32>(pub F, pub PhantomData<I>);
+
+impl<F, I, const FRAC: u32> Fixed<F, I, FRAC> {
+    /// Wraps a value.
+    pub const fn new(value: F) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+macro_rules! impl_for {
+    ($int:ty) => {
+        impl<F, const FRAC: u32> Fixed<F, $int, FRAC> {
+            const VALID: () = assert!(FRAC < <$int>::BITS, "FRAC must be less than I::BITS");
+
+            // powers of 2 are represented exactly
+            #[allow(clippy::cast_precision_loss)]
+            const SCALE: f64 = (1u64 << FRAC) as f64;
+        }
+
+        impl<F, const FRAC: u32> FixedEncodeLen for Fixed<F, $int, FRAC> {
+            const ENCODE_LEN: usize = size_of::<$int>();
+        }
+
+        impl<F: Float, const FRAC: u32> Decode for Fixed<F, $int, FRAC> {
+            type Error = Infallible;
+
+            #[inline]
+            #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+            fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                let () = Self::VALID;
+                let raw = src.read::<$int>()?;
+                Ok(Self::new(F::from_f64(raw as f64 / Self::SCALE)))
+            }
+        }
+
+        impl<F: Float, const FRAC: u32> Encode for Fixed<F, $int, FRAC> {
+            type Error = OutOfRange;
+
+            #[inline]
+            #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+            fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+                let () = Self::VALID;
+                let scaled = self.0.to_f64() * Self::SCALE;
+                // anything in this range rounds to a value in `$int`'s range
+                // also rejects NaN
+                let min = <$int>::MIN as f64 - 0.5;
+                let max = <$int>::MAX as f64 + 0.5;
+                if !(scaled >= min && scaled < max) {
+                    return Err(OutOfRange.into());
+                }
+
+                let raw = <$int>::try_from(round(scaled)).map_err(|_| OutOfRange)?;
+                dst.write(raw).map_err(BufTooShort::from)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_for!(u8);
+impl_for!(i8);
+impl_for!(u16);
+impl_for!(i16);
+impl_for!(u32);
+impl_for!(i32);
+impl_for!(u64);
+impl_for!(i64);
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::BytesMut};
+
+    #[test]
+    fn exact() {
+        crate::test::hint_round_trip(&Fixed::<f32, i16, 8>::new(-3.25));
+        crate::test::hint_round_trip(&Fixed::<f64, u8, 4>::new(15.9375));
+        crate::test::hint_round_trip(&Fixed::<f64, i32, 16>::new(-32768.0));
+        crate::test::hint_round_trip(&Fixed::<f32, i64, 0>::new(0.0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(Fixed::<f32, i16, 8>::new(1.0), "0100");
+        crate::assert_encodes!(Fixed::<f32, i16, 8>::new(-1.0), "ff00");
+        crate::assert_encodes!(Fixed::<f32, u8, 4>::new(1.5), "18");
+    }
+
+    #[test]
+    fn bounded_error() {
+        for value in [0.1, -0.1, 1.0 / 3.0, 100.123] {
+            let mut buf = BytesMut::new();
+            buf.write(Fixed::<f64, i32, 10>::new(value)).unwrap();
+            let Fixed(actual, _) = buf.freeze().read::<Fixed<f64, i32, 10>>().unwrap();
+            assert!((actual - value).abs() <= 1.0 / 2048.0);
+        }
+    }
+
+    #[test]
+    fn encode_out_of_range() {
+        for value in [128.0, -128.01, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                BufTooShortOr::Or(OutOfRange),
+                BytesMut::new()
+                    .write(Fixed::<f32, i16, 8>::new(value))
+                    .unwrap_err()
+            );
+        }
+        assert_eq!(
+            BufTooShortOr::Or(OutOfRange),
+            BytesMut::new()
+                .write(Fixed::<f32, u8, 0>::new(-1.0))
+                .unwrap_err()
+        );
+        // rounds to the edge of the range
+        BytesMut::new()
+            .write(Fixed::<f32, i16, 8>::new(127.998))
+            .unwrap();
+        BytesMut::new()
+            .write(Fixed::<f32, u8, 0>::new(-0.4))
+            .unwrap();
+    }
+}
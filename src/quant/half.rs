@@ -0,0 +1,106 @@
+use {
+    super::Float,
+    crate::{BufTooShortOr, Decode, Encode, FixedEncodeLen, Read, Write},
+    core::convert::Infallible,
+    half::{bf16, f16},
+};
+
+macro_rules! impl_half {
+    ($(#[$meta:meta])* $name:ident, $half:ty) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+        #[allow(clippy::derive_partial_eq_without_eq)] // `F` is a float
+        pub struct $name<F>(pub F);
+
+        impl<F> FixedEncodeLen for $name<F> {
+            const ENCODE_LEN: usize = <$half>::ENCODE_LEN;
+        }
+
+        impl<F: Float> Decode for $name<F> {
+            type Error = Infallible;
+
+            #[inline]
+            fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                let value = src.read::<$half>()?;
+                Ok(Self(F::from_f64(value.to_f64())))
+            }
+        }
+
+        impl<F: Float> Encode for $name<F> {
+            type Error = Infallible;
+
+            #[inline]
+            fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+                dst.write(<$half>::from_f64(self.0.to_f64()))
+            }
+        }
+    };
+}
+
+impl_half!(
+    /// Floating-point value encoded as an IEEE 754 half-precision [`f16`].
+    ///
+    /// The value is rounded to the nearest `f16` on encode. `f16` has 11
+    /// bits of precision and a maximum value of 65504 - values larger than this
+    /// become infinity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use octs::{BytesMut, Read, Write, quant::F16};
+    /// let mut buf = BytesMut::new();
+    /// buf.write(F16(0.1f32)).unwrap();
+    /// assert_eq!(2, buf.len());
+    ///
+    /// let F16(value) = buf.freeze().read::<F16<f32>>().unwrap();
+    /// assert!((value - 0.1).abs() < 0.0001);
+    /// ```
+    ///
+    /// [`f16`]: struct@half::f16
+    F16,
+    f16
+);
+
+impl_half!(
+    /// Floating-point value encoded as a [`bf16`] "brain floating point".
+    ///
+    /// The value is rounded to the nearest [`bf16`] on encode. [`bf16`] has the
+    /// same range as [`f32`], but only 8 bits of precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use octs::{BytesMut, Read, Write, quant::Bf16};
+    /// let mut buf = BytesMut::new();
+    /// buf.write(Bf16(1.0e30f32)).unwrap();
+    /// assert_eq!(2, buf.len());
+    ///
+    /// let Bf16(value) = buf.freeze().read::<Bf16<f32>>().unwrap();
+    /// assert!((value / 1.0e30 - 1.0).abs() < 0.01);
+    /// ```
+    Bf16,
+    bf16
+);
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    #[test]
+    fn exact() {
+        for v in [0.0f32, 1.0, -2.5, 0.125, f32::INFINITY] {
+            hint_round_trip(&F16(v));
+            hint_round_trip(&Bf16(v));
+            hint_round_trip(&F16(f64::from(v)));
+            hint_round_trip(&Bf16(f64::from(v)));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(F16(1.0f32), "3c00");
+        crate::assert_encodes!(Bf16(1.0f32), "3f80");
+        crate::assert_encodes!(F16(100_000.0f32), "7c00");
+    }
+}
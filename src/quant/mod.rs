@@ -0,0 +1,96 @@
+//! Lossy encodings for floating-point numbers which take up fewer bytes than
+//! their raw IEEE 754 representation.
+//!
+//! [`f32`] and [`f64`] are always encoded as 4 and 8 bytes respectively. When
+//! you know the range and precision that a value needs, such as for positions
+//! and rotations in game netcode, you can encode it in fewer bytes using:
+//! * [`Quantized`] - maps a value in a known range onto an integer of a given
+//!   number of bits
+//! * [`Fixed`] - stores a value as a fixed-point integer with a given number of
+//!   fractional bits
+//! * `F16` and `Bf16` - converts a value to a 2-byte half-precision float;
+//!   requires the `half` feature
+//!
+//! All of these types round to the nearest representable value on encode, so
+//! decoding gives back the original value with a bounded error. [`Quantized`]
+//! and [`Fixed`] fail to encode values outside of their range with
+//! [`OutOfRange`], whereas `F16` and `Bf16` never fail, and encode values
+//! which are too large as infinity.
+
+mod error;
+mod fixed;
+#[cfg(feature = "half")]
+mod half;
+mod quantized;
+
+#[cfg(feature = "half")]
+pub use half::*;
+pub use {error::*, fixed::*, quantized::*};
+
+/// Floating-point type which can be stored in a [`Quantized`] or [`Fixed`].
+pub trait Float: Copy {
+    /// Converts this value to an [`f64`].
+    fn to_f64(self) -> f64;
+
+    /// Converts an [`f64`] to a value of this type, possibly losing precision.
+    fn from_f64(v: f64) -> Self;
+}
+
+impl Float for f32 {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_f64(v: f64) -> Self {
+        v as Self
+    }
+}
+
+impl Float for f64 {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+}
+
+/// Rounds `v` to the nearest integer, with ties rounding away from zero.
+///
+/// `f64::round` is not available in `core`, so we do this ourselves. `v` must
+/// be within `i128`'s range.
+#[inline]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn round(v: f64) -> i128 {
+    let trunc = v as i128;
+    let frac = v - trunc as f64;
+    if frac >= 0.5 {
+        trunc + 1
+    } else if frac <= -0.5 {
+        trunc - 1
+    } else {
+        trunc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounding() {
+        assert_eq!(0, round(0.0));
+        assert_eq!(0, round(0.49));
+        assert_eq!(1, round(0.5));
+        assert_eq!(1, round(1.49));
+        assert_eq!(0, round(-0.49));
+        assert_eq!(-1, round(-0.5));
+        assert_eq!(-2, round(-1.5));
+    }
+}
@@ -0,0 +1,201 @@
+use {
+    super::{Float, OutOfRange, round},
+    crate::{BufTooShortOr, Decode, Encode, FixedEncodeLen, Read, Write, prim::InvalidValue},
+};
+
+/// Floating-point value in the range `MIN..=MAX`, encoded as an integer of
+/// `BITS` bits.
+///
+/// The range is evenly divided into `2^BITS - 1` steps, and the value is
+/// rounded to the nearest step on encode. This means that after a round trip,
+/// the value will be within half a step of the original:
+///
+/// ```text
+/// |decoded - original| <= (MAX - MIN) / (2^BITS - 1) / 2
+/// ```
+///
+/// The value is encoded in the minimum number of whole bytes required to hold
+/// `BITS` bits, in big-endian order.
+///
+/// `MIN` must be less than `MAX`, and `BITS` must be in `1..=32`, so that the
+/// rounding error of [`f64`] arithmetic stays well below the size of a step.
+/// These are checked at compile time when encoding or decoding.
+///
+/// # Errors
+///
+/// Encoding a value outside of `MIN..=MAX`, or NaN, fails with [`OutOfRange`].
+/// Decoding an integer larger than `2^BITS - 1` fails with [`InvalidValue`].
+///
+/// # Examples
+///
+/// ```
+/// # use octs::{BytesMut, Read, Write, quant::Quantized};
+/// // an angle in degrees, to within ~0.003 degrees, in 2 bytes
+/// type Angle = Quantized<f32, -180, 180, 16>;
+///
+/// let mut buf = BytesMut::new();
+/// buf.write(Angle::new(45.0)).unwrap();
+/// assert_eq!(2, buf.len());
+///
+/// let Quantized(angle) = buf.freeze().read::<Angle>().unwrap();
+/// assert!((angle - 45.0).abs() < 0.003);
+///
+/// // out of range
+/// BytesMut::new().write(Angle::new(200.0)).unwrap_err();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[allow(clippy::derive_partial_eq_without_eq)] // `F` is a float
+pub struct Quantized<F, const MIN: i32, const MAX: i32, const BITS: u32>(pub F);
+
+impl<F, const MIN: i32, const MAX: i32, const BITS: u32> Quantized<F, MIN, MAX, BITS> {
+    const VALID: () = {
+        assert!(MIN < MAX, "MIN must be less than MAX");
+        assert!(BITS >= 1 && BITS <= 32, "BITS must be in 1..=32");
+    };
+
+    /// Maximum integer that a value is encoded as.
+    pub const MAX_STEP: u64 = (1 << BITS) - 1;
+
+    /// Wraps a value.
+    pub const fn new(value: F) -> Self {
+        Self(value)
+    }
+
+    /// Gets the maximum difference between a value before and after being
+    /// encoded and decoded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn max_error() -> f64 {
+        (f64::from(MAX) - f64::from(MIN)) / Self::MAX_STEP as f64 / 2.0
+    }
+}
+
+impl<F, const MIN: i32, const MAX: i32, const BITS: u32> FixedEncodeLen
+    for Quantized<F, MIN, MAX, BITS>
+{
+    const ENCODE_LEN: usize = BITS.div_ceil(8) as usize;
+}
+
+impl<F: Float, const MIN: i32, const MAX: i32, const BITS: u32> Decode
+    for Quantized<F, MIN, MAX, BITS>
+{
+    type Error = InvalidValue;
+
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let () = Self::VALID;
+        if src.remaining() < Self::ENCODE_LEN {
+            return Err(BufTooShortOr::TooShort);
+        }
+        let mut bytes = [0u8; 8];
+        src.copy_to_slice(&mut bytes[8 - Self::ENCODE_LEN..]);
+        let step = u64::from_be_bytes(bytes);
+        if step > Self::MAX_STEP {
+            return Err(InvalidValue(()).into());
+        }
+
+        let (min, max) = (f64::from(MIN), f64::from(MAX));
+        let value = min + (step as f64 / Self::MAX_STEP as f64) * (max - min);
+        Ok(Self(F::from_f64(value)))
+    }
+}
+
+impl<F: Float, const MIN: i32, const MAX: i32, const BITS: u32> Encode
+    for Quantized<F, MIN, MAX, BITS>
+{
+    type Error = OutOfRange;
+
+    #[inline]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        let () = Self::VALID;
+        let (min, max) = (f64::from(MIN), f64::from(MAX));
+        let value = self.0.to_f64();
+        // also rejects NaN
+        if !(value >= min && value <= max) {
+            return Err(OutOfRange.into());
+        }
+
+        let step = round((value - min) / (max - min) * Self::MAX_STEP as f64) as u64;
+        dst.write_from(&step.to_be_bytes()[8 - Self::ENCODE_LEN..])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::BytesMut};
+
+    fn round_trip<const MIN: i32, const MAX: i32, const BITS: u32>(value: f64) {
+        let mut buf = BytesMut::new();
+        buf.write(Quantized::<f64, MIN, MAX, BITS>(value)).unwrap();
+        assert_eq!(Quantized::<f64, MIN, MAX, BITS>::ENCODE_LEN, buf.len());
+
+        let Quantized(actual) = buf
+            .freeze()
+            .read::<Quantized<f64, MIN, MAX, BITS>>()
+            .unwrap();
+        let max_error = Quantized::<f64, MIN, MAX, BITS>::max_error();
+        assert!(
+            (actual - value).abs() <= max_error,
+            "expected = {value}, actual = {actual}, max_error = {max_error}"
+        );
+    }
+
+    #[test]
+    fn bounded_error() {
+        for value in [-180.0, -179.99, -1.0, 0.0, 0.001, 45.0, 179.99, 180.0] {
+            round_trip::<-180, 180, 16>(value);
+            round_trip::<-180, 180, 8>(value);
+            round_trip::<-180, 180, 12>(value);
+            round_trip::<-180, 180, 32>(value);
+        }
+        for value in [0.0, 0.25, 0.5, 1.0] {
+            round_trip::<0, 1, 1>(value);
+            round_trip::<0, 1, 10>(value);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn exact_at_bounds() {
+        let mut buf = BytesMut::new();
+        buf.write(Quantized::<f32, -10, 10, 12>(-10.0)).unwrap();
+        buf.write(Quantized::<f32, -10, 10, 12>(10.0)).unwrap();
+        crate::assert_encodes!(Quantized::<f32, -10, 10, 12>(-10.0), "0000");
+        crate::assert_encodes!(Quantized::<f32, -10, 10, 12>(10.0), "0fff");
+
+        let mut buf = buf.freeze();
+        assert_eq!(-10.0, buf.read::<Quantized<f32, -10, 10, 12>>().unwrap().0);
+        assert_eq!(10.0, buf.read::<Quantized<f32, -10, 10, 12>>().unwrap().0);
+    }
+
+    #[test]
+    fn encode_out_of_range() {
+        for value in [-10.01, 10.01, f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(
+                BufTooShortOr::Or(OutOfRange),
+                BytesMut::new()
+                    .write(Quantized::<f32, -10, 10, 12>(value))
+                    .unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        (&[0x10, 0x00][..])
+            .read::<Quantized<f32, -10, 10, 12>>()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn decode_malformed() {
+        crate::test::truncated(&Quantized::<f32, -10, 10, 24>(1.0));
+    }
+}
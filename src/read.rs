@@ -1,6 +1,6 @@
 use {
-    crate::{BufTooShort, BufTooShortOr},
-    bytes::{Buf, Bytes},
+    crate::{BufTooShort, BufTooShortOr, SectionError, TrailingBytes, prim::Number},
+    bytes::{Buf, Bytes, buf::Take},
 };
 
 /// Allows reading bytes from a buffer.
@@ -72,6 +72,42 @@ pub trait Read: Buf {
         Ok(buf)
     }
 
+    /// Attempts to fill `dst` with the next values of `T` in the buffer.
+    ///
+    /// This is equivalent to reading each value of `T` individually, but
+    /// checks that there are enough bytes remaining only once, and decodes
+    /// values in blocks.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are not enough bytes remaining in the buffer to fill all
+    /// of `dst`, in which case nothing is read.
+    #[inline]
+    fn read_into<T: Number>(&mut self, dst: &mut [T]) -> Result<(), BufTooShort> {
+        // number of bytes buffered on the stack at once
+        const BLOCK_LEN: usize = 256;
+
+        let len = dst.len().checked_mul(T::ENCODE_LEN).ok_or(BufTooShort)?;
+        if self.remaining() < len {
+            return Err(BufTooShort);
+        }
+
+        let mut block = [0u8; BLOCK_LEN];
+        for dst in dst.chunks_mut(BLOCK_LEN / T::ENCODE_LEN) {
+            let len = dst.len() * T::ENCODE_LEN;
+            // decode straight from the buffer if we can
+            if let Some(src) = self.chunk().get(..len) {
+                T::decode_slice(src, dst);
+                self.advance(len);
+            } else {
+                let src = &mut block[..len];
+                self.copy_to_slice(src);
+                T::decode_slice(src, dst);
+            }
+        }
+        Ok(())
+    }
+
     /// Attempts to read the next `T` in the buffer.
     ///
     /// # Errors
@@ -85,6 +121,128 @@ pub trait Read: Buf {
     {
         T::decode(self)
     }
+
+    /// Attempts to read the next `T` in the buffer, using `ctx` as context.
+    ///
+    /// Any [`Decode`] type can also be read with this function, in which case
+    /// `ctx` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are not enough bytes remaining in the buffer, or if a
+    /// value of `T` could not be read from the bytes in the buffer.
+    #[inline]
+    fn read_with<T: DecodeWith<C>, C: ?Sized>(
+        &mut self,
+        ctx: &C,
+    ) -> Result<T, BufTooShortOr<T::Error>>
+    where
+        Self: Sized,
+    {
+        T::decode_with(self, ctx)
+    }
+
+    /// Attempts to create a reader over only the next `n` bytes of this buffer.
+    ///
+    /// Reading from the returned [`Take`] advances this buffer, but the
+    /// returned reader never reads past those `n` bytes. Use this when a
+    /// message declares how long its body is, so that decoding the body can't
+    /// read into whatever comes after it.
+    ///
+    /// Bytes of the section which are not read are left in this buffer. To
+    /// decode a value from exactly `n` bytes, use [`Read::read_section`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are less than `n` bytes remaining in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use octs::Read;
+    ///
+    /// let mut buf = &[0x12, 0x34, 0x56][..];
+    /// let mut section = buf.take_section(2).unwrap();
+    /// assert_eq!(0x12, section.read::<u8>().unwrap());
+    /// // would read past the end of the section
+    /// section.read::<u16>().unwrap_err();
+    /// assert_eq!(0x34, section.read::<u8>().unwrap());
+    ///
+    /// assert_eq!(0x56, buf.read::<u8>().unwrap());
+    /// ```
+    #[inline]
+    fn take_section(&mut self, n: usize) -> Result<Take<&mut Self>, BufTooShort>
+    where
+        Self: Sized,
+    {
+        if self.remaining() >= n {
+            Ok(Buf::take(self, n))
+        } else {
+            Err(BufTooShort)
+        }
+    }
+
+    /// Attempts to read a `T` from exactly the next `n` bytes in the buffer.
+    ///
+    /// `T` can't read past the end of the section, and must consume all `n`
+    /// bytes of it.
+    ///
+    /// Once there are at least `n` bytes in the buffer, the whole section is
+    /// always skipped, even if decoding `T` fails, so the next value can be
+    /// read after the error.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`BufTooShortOr::TooShort`] if there are less than `n` bytes
+    /// remaining in the buffer, in which case nothing is consumed.
+    ///
+    /// Otherwise, errors with:
+    /// * [`SectionError::Overrun`] if `T` tried to read past the end of the
+    ///   section
+    /// * [`SectionError::Inner`] if a value of `T` could not be read from the
+    ///   section
+    /// * [`SectionError::TrailingBytes`] if `T` did not consume all `n` bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use octs::{BufTooShortOr, Read, SectionError, TrailingBytes};
+    ///
+    /// let mut buf = &[0x12, 0x34, 0x56, 0x78, 0x9a][..];
+    /// assert_eq!(0x1234, buf.read_section::<u16>(2).unwrap());
+    /// assert_eq!(
+    ///     Err(BufTooShortOr::Or(SectionError::TrailingBytes(TrailingBytes {
+    ///         remaining: 1
+    ///     }))),
+    ///     buf.read_section::<u8>(2),
+    /// );
+    /// assert_eq!(0x9a, buf.read::<u8>().unwrap());
+    /// ```
+    #[inline]
+    fn read_section<T: Decode>(
+        &mut self,
+        n: usize,
+    ) -> Result<T, BufTooShortOr<SectionError<T::Error>>>
+    where
+        Self: Sized,
+    {
+        let mut section = self.take_section(n)?;
+        let result = T::decode(&mut section);
+        let remaining = section.remaining();
+        section.advance(remaining);
+
+        let value = result.map_err(|e| match e {
+            // we already know the buffer has enough bytes for the section
+            BufTooShortOr::TooShort => BufTooShortOr::Or(SectionError::Overrun),
+            BufTooShortOr::Or(err) => BufTooShortOr::Or(SectionError::Inner(err)),
+        })?;
+        if remaining > 0 {
+            return Err(BufTooShortOr::Or(SectionError::TrailingBytes(
+                TrailingBytes { remaining },
+            )));
+        }
+        Ok(value)
+    }
 }
 
 impl<T: Buf + ?Sized> Read for T {}
@@ -104,6 +262,40 @@ pub trait Decode: Sized {
     fn decode(src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>>;
 }
 
+/// Allows reading a value of this type from a [`Read`], using some context
+/// of type `C`.
+///
+/// Use this instead of [`Decode`] when how a value is decoded depends on some
+/// state outside of the buffer, such as a negotiated protocol version, or a
+/// string table shared between the two sides of a connection.
+///
+/// All [`Decode`] types implement this trait for any `C`, ignoring the
+/// context.
+pub trait DecodeWith<C: ?Sized>: Sized {
+    /// Error type of [`DecodeWith::decode_with`], excluding [`BufTooShort`]
+    /// errors.
+    type Error;
+
+    /// Attempts to decode a value of this type from a [`Read`], using `ctx` as
+    /// context.
+    ///
+    /// # Errors
+    ///
+    /// If there are not enough bytes in `src` to read a value of this type,
+    /// [`BufTooShortOr::TooShort`] is returned. Otherwise, it is up to the
+    /// implementation on what the returned error represents.
+    fn decode_with(src: impl Read, ctx: &C) -> Result<Self, BufTooShortOr<Self::Error>>;
+}
+
+impl<T: Decode, C: ?Sized> DecodeWith<C> for T {
+    type Error = T::Error;
+
+    #[inline]
+    fn decode_with(src: impl Read, _: &C) -> Result<Self, BufTooShortOr<Self::Error>> {
+        T::decode(src)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,4 +376,125 @@ mod tests {
         test_with([1, 2].chain(&[3, 4][..]));
         test_with(Bytes::from_static(&[1, 2]).chain(Bytes::from_static(&[3, 4])));
     }
+
+    struct Protocol {
+        version: u8,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Id(u32);
+
+    impl DecodeWith<Protocol> for Id {
+        type Error = core::convert::Infallible;
+
+        fn decode_with(
+            mut src: impl Read,
+            ctx: &Protocol,
+        ) -> Result<Self, BufTooShortOr<Self::Error>> {
+            if ctx.version >= 2 {
+                Ok(Self(src.read::<u32>()?))
+            } else {
+                Ok(Self(u32::from(src.read::<u16>()?)))
+            }
+        }
+    }
+
+    impl crate::EncodeWith<Protocol> for Id {
+        type Error = core::convert::Infallible;
+
+        fn encode_with(
+            &self,
+            mut dst: impl crate::Write,
+            ctx: &Protocol,
+        ) -> Result<(), BufTooShortOr<Self::Error>> {
+            if ctx.version >= 2 {
+                dst.write(self.0)
+            } else {
+                #[allow(clippy::cast_possible_truncation)]
+                dst.write(self.0 as u16)
+            }
+        }
+    }
+
+    #[test]
+    fn read_with() {
+        use crate::{BytesMut, Write};
+
+        let v1 = Protocol { version: 1 };
+        let v2 = Protocol { version: 2 };
+
+        let mut buf = BytesMut::new();
+        buf.write_with(&Id(5), &v1).unwrap();
+        buf.write_with(&Id(6), &v2).unwrap();
+        // context-free types can still be written with a context
+        buf.write_with(&7u8, &v2).unwrap();
+        assert_eq!(&[0, 5, 0, 0, 0, 6, 7], &buf[..]);
+
+        let mut buf = buf.freeze();
+        assert_eq!(Id(5), buf.read_with::<Id, _>(&v1).unwrap());
+        assert_eq!(Id(6), buf.read_with::<Id, _>(&v2).unwrap());
+        assert_eq!(7, buf.read_with::<u8, _>(&v2).unwrap());
+        assert!(!buf.has_remaining());
+    }
+
+    #[test]
+    fn take_section() {
+        fn test_with(mut buf: impl Read) {
+            let mut section = buf.take_section(3).unwrap();
+            assert_eq!(3, section.remaining());
+            assert_eq!([1, 2], section.read_exact::<2>().unwrap());
+            section.read_exact::<2>().unwrap_err();
+            assert_eq!(1, section.remaining());
+            assert_eq!(3, buf.remaining());
+
+            assert!(buf.take_section(4).is_err());
+            assert_eq!(3, buf.remaining());
+            assert_eq!(
+                [3, 4, 5],
+                buf.take_section(3).unwrap().read_exact().unwrap()
+            );
+            assert!(!buf.has_remaining());
+        }
+
+        test_with(&[1, 2, 3, 4, 5][..]);
+        test_with([1, 2].chain(&[3, 4, 5][..]));
+    }
+
+    #[test]
+    fn read_section() {
+        use crate::VarInt;
+
+        let mut buf = Bytes::from_static(&[0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc]);
+        assert_eq!(0x1234, buf.read_section::<u16>(2).unwrap());
+        assert_eq!(4, buf.remaining());
+
+        // reads past the end of the section, even though the buffer has more
+        assert_eq!(
+            Err(BufTooShortOr::Or(SectionError::Overrun)),
+            buf.read_section::<u32>(3)
+        );
+        assert_eq!(1, buf.remaining());
+
+        // section longer than the buffer, so nothing is consumed
+        assert_eq!(Err(BufTooShortOr::TooShort), buf.read_section::<u8>(2));
+        assert_eq!(1, buf.remaining());
+
+        let mut buf = Bytes::from_static(&[0x12, 0x34, 0x56, 0x78]);
+        // doesn't consume the whole section, but the section is still skipped
+        assert_eq!(
+            Err(BufTooShortOr::Or(SectionError::TrailingBytes(
+                TrailingBytes { remaining: 2 }
+            ))),
+            buf.read_section::<u8>(3)
+        );
+        assert_eq!(0x78, buf.read::<u8>().unwrap());
+
+        // inner errors are passed through, and the section is still skipped
+        let mut buf = &[0xff; 12][..];
+        assert!(matches!(
+            buf.read_section::<VarInt<u64>>(11),
+            Err(BufTooShortOr::Or(SectionError::Inner(_)))
+        ));
+        assert_eq!(1, buf.remaining());
+    }
 }
@@ -0,0 +1,103 @@
+use {
+    crate::BufError,
+    core::{
+        error::Error,
+        fmt::{Debug, Display},
+    },
+};
+
+/// Attempted to decode a message from a [`Registry`], but no message type was
+/// registered for the ID which was read.
+///
+/// [`Registry`]: crate::registry::Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnknownId<I>(pub I);
+
+impl<I: Debug> Display for UnknownId<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown message id {:?}", self.0)
+    }
+}
+
+impl<I> BufError for UnknownId<I> {}
+
+impl<I: Debug> Error for UnknownId<I> {}
+
+/// Attempted to encode a message using a [`Registry`], but its type was not
+/// registered.
+///
+/// [`Registry`]: crate::registry::Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnregisteredType;
+
+impl Display for UnregisteredType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "message type not registered")
+    }
+}
+
+impl BufError for UnregisteredType {}
+
+impl Error for UnregisteredType {}
+
+/// Error when decoding a message using a [`Registry`].
+///
+/// [`Registry`]: crate::registry::Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistryDecodeError<I, E> {
+    /// See [`UnknownId`].
+    UnknownId(UnknownId<I>),
+    /// Failed to decode the ID or the message.
+    Inner(E),
+}
+
+impl<I: Debug, E: Display> Display for RegistryDecodeError<I, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownId(err) => write!(f, "{err}"),
+            Self::Inner(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<I, E> BufError for RegistryDecodeError<I, E> {}
+
+impl<I: Debug + 'static, E: Error + 'static> Error for RegistryDecodeError<I, E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownId(err) => Some(err),
+            Self::Inner(err) => Some(err),
+        }
+    }
+}
+
+/// Error when encoding a message using a [`Registry`].
+///
+/// [`Registry`]: crate::registry::Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistryEncodeError<E> {
+    /// See [`UnregisteredType`].
+    Unregistered(UnregisteredType),
+    /// Failed to encode the message.
+    Inner(E),
+}
+
+impl<E: Display> Display for RegistryEncodeError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unregistered(err) => write!(f, "{err}"),
+            Self::Inner(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E> BufError for RegistryEncodeError<E> {}
+
+impl<E: Error + 'static> Error for RegistryEncodeError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unregistered(err) => Some(err),
+            Self::Inner(err) => Some(err),
+        }
+    }
+}
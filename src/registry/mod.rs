@@ -0,0 +1,434 @@
+//! Dispatches messages to their types based on an ID written before them.
+//!
+//! Protocols often send many types of messages over the same stream, each
+//! prefixed with an ID which identifies its type. Instead of writing a `match`
+//! on this ID by hand, register each message type with its ID in a
+//! [`Registry`] at startup. The registry then:
+//! * when decoding, reads the ID, and decodes the message type registered for
+//!   it into a common message type `M`
+//! * when encoding, looks up the ID for the message's type, and writes it
+//!   before the message
+//!
+//! `M` is usually an `enum` with a variant for each message type, but can also
+//! be a type-erased message like a `Box<dyn Any>`, using
+//! [`Registry::register_with`]. Errors from decoding the ID and each message
+//! type are converted into a common error type `E`.
+//!
+//! # Wire format
+//!
+//! * ID as an `I`
+//! * message
+//!
+//! # Examples
+//!
+//! ```
+//! use octs::{
+//!     BufTooShortOr, BytesMut, Decode, Encode, FixedEncodeLen, Read, VarInt, VarIntTooLarge,
+//!     Write,
+//!     registry::{Registry, RegistryDecodeError},
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Ping(u32);
+//!
+//! impl FixedEncodeLen for Ping {
+//!     const ENCODE_LEN: usize = 4;
+//! }
+//!
+//! impl Encode for Ping {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+//!         dst.write(self.0)
+//!     }
+//! }
+//!
+//! impl Decode for Ping {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+//!         Ok(Self(src.read()?))
+//!     }
+//! }
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Message {
+//!     Ping(Ping),
+//!     Flag(bool),
+//! }
+//!
+//! impl From<Ping> for Message {
+//!     fn from(value: Ping) -> Self {
+//!         Self::Ping(value)
+//!     }
+//! }
+//!
+//! #[derive(Debug)]
+//! enum Error {
+//!     Id(VarIntTooLarge),
+//!     Flag(octs::prim::InvalidValue),
+//! }
+//!
+//! impl From<VarIntTooLarge> for Error {
+//!     fn from(value: VarIntTooLarge) -> Self {
+//!         Self::Id(value)
+//!     }
+//! }
+//!
+//! impl From<octs::prim::InvalidValue> for Error {
+//!     fn from(value: octs::prim::InvalidValue) -> Self {
+//!         Self::Flag(value)
+//!     }
+//! }
+//!
+//! impl From<core::convert::Infallible> for Error {
+//!     fn from(value: core::convert::Infallible) -> Self {
+//!         match value {}
+//!     }
+//! }
+//!
+//! let mut registry = Registry::<VarInt<u16>, Message, Error>::new();
+//! registry
+//!     .register::<Ping>(VarInt(0))
+//!     .register_with::<bool>(VarInt(1), Message::Flag);
+//!
+//! let mut buf = BytesMut::new();
+//! registry.encode(&Ping(42), &mut buf).unwrap();
+//! registry.encode(&true, &mut buf).unwrap();
+//!
+//! let mut buf = buf.freeze();
+//! assert_eq!(Message::Ping(Ping(42)), registry.decode(&mut buf).unwrap());
+//! assert_eq!(Message::Flag(true), registry.decode(&mut buf).unwrap());
+//!
+//! // unknown IDs are reported as errors
+//! let mut buf = &[0x05][..];
+//! assert!(matches!(
+//!     registry.decode(&mut buf),
+//!     Err(BufTooShortOr::Or(RegistryDecodeError::UnknownId(_)))
+//! ));
+//! ```
+
+mod error;
+
+pub use error::*;
+use {
+    crate::{Buf, BufTooShortOr, Decode, Encode, EncodeLen, Read, Write},
+    alloc::{boxed::Box, collections::BTreeMap},
+    core::{
+        any::{TypeId, type_name},
+        convert::Infallible,
+        fmt::{self, Debug},
+    },
+};
+
+type DecodeFn<M, E> = Box<dyn Fn(&mut dyn Buf) -> Result<M, BufTooShortOr<E>> + Send + Sync>;
+
+/// Maps message IDs of type `I` to the message types registered for them.
+///
+/// See the [module-level documentation](self).
+pub struct Registry<I, M, E> {
+    decoders: BTreeMap<I, DecodeFn<M, E>>,
+    ids: BTreeMap<TypeId, I>,
+}
+
+impl<I: Debug, M, E> Debug for Registry<I, M, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("ids", &self.decoders.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, M, E> Default for Registry<I, M, E> {
+    fn default() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+            ids: BTreeMap::new(),
+        }
+    }
+}
+
+impl<I: Ord + Clone + Debug, M, E> Registry<I, M, E> {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` as the message type for `id`, converting it into `M` using
+    /// [`From`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` or `T` is already registered.
+    pub fn register<T>(&mut self, id: I) -> &mut Self
+    where
+        T: Decode + 'static,
+        M: From<T> + 'static,
+        E: From<T::Error> + 'static,
+    {
+        self.register_with::<T>(id, M::from)
+    }
+
+    /// Registers `T` as the message type for `id`, converting it into `M` using
+    /// `map`.
+    ///
+    /// Use this when `M` can't implement [`From<T>`], such as when it is a
+    /// type-erased message like a `Box<dyn Any>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` or `T` is already registered.
+    pub fn register_with<T>(&mut self, id: I, map: fn(T) -> M) -> &mut Self
+    where
+        T: Decode + 'static,
+        M: 'static,
+        E: From<T::Error> + 'static,
+    {
+        assert!(
+            !self.decoders.contains_key(&id),
+            "id {id:?} is already registered"
+        );
+        assert!(
+            !self.ids.contains_key(&TypeId::of::<T>()),
+            "type `{}` is already registered",
+            type_name::<T>()
+        );
+
+        self.ids.insert(TypeId::of::<T>(), id.clone());
+        self.decoders.insert(
+            id,
+            Box::new(move |src| T::decode(src).map(map).map_err(|err| err.map_or(E::from))),
+        );
+        self
+    }
+
+    /// Gets the ID that `T` is registered with.
+    #[must_use]
+    pub fn id_of<T: 'static>(&self) -> Option<&I> {
+        self.ids.get(&TypeId::of::<T>())
+    }
+
+    /// Reads an ID from `src`, then decodes the message type registered for it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the ID or message could not be decoded, or if there is no
+    /// message type registered for the ID.
+    pub fn decode(&self, mut src: impl Read) -> Result<M, BufTooShortOr<RegistryDecodeError<I, E>>>
+    where
+        I: Decode,
+        E: From<I::Error>,
+    {
+        let id = src
+            .read::<I>()
+            .map_err(|err| err.map_or(|err| RegistryDecodeError::Inner(E::from(err))))?;
+        let Some(decode) = self.decoders.get(&id) else {
+            return Err(BufTooShortOr::Or(RegistryDecodeError::UnknownId(
+                UnknownId(id),
+            )));
+        };
+        decode(&mut src).map_err(|err| err.map_or(RegistryDecodeError::Inner))
+    }
+
+    /// Writes the ID that `T` is registered with into `dst`, followed by
+    /// `value`.
+    ///
+    /// Nothing is written if there is not enough space in `dst` for both the
+    /// ID and `value`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if `T` is not registered, or if `value` could not be encoded.
+    pub fn encode<T>(
+        &self,
+        value: &T,
+        mut dst: impl Write,
+    ) -> Result<(), BufTooShortOr<RegistryEncodeError<T::Error>>>
+    where
+        T: EncodeLen + 'static,
+        I: EncodeLen + Encode<Error = Infallible>,
+    {
+        let id = self
+            .id_of::<T>()
+            .ok_or(BufTooShortOr::Or(RegistryEncodeError::Unregistered(
+                UnregisteredType,
+            )))?;
+        if dst.remaining_mut() < id.encode_len() + value.encode_len() {
+            return Err(BufTooShortOr::TooShort);
+        }
+        dst.write(id)?;
+        dst.write(value)
+            .map_err(|err| err.map_or(RegistryEncodeError::Inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{BytesMut, StackBuf, VarInt, VarIntTooLarge, prim::InvalidValue},
+        alloc::{boxed::Box, vec::Vec},
+        core::any::Any,
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum Message {
+        Num(u32),
+        Flag(bool),
+    }
+
+    impl From<u32> for Message {
+        fn from(value: u32) -> Self {
+            Self::Num(value)
+        }
+    }
+
+    impl From<bool> for Message {
+        fn from(value: bool) -> Self {
+            Self::Flag(value)
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Id(VarIntTooLarge),
+        Flag(InvalidValue),
+    }
+
+    impl From<VarIntTooLarge> for Error {
+        fn from(value: VarIntTooLarge) -> Self {
+            Self::Id(value)
+        }
+    }
+
+    impl From<InvalidValue> for Error {
+        fn from(value: InvalidValue) -> Self {
+            Self::Flag(value)
+        }
+    }
+
+    impl From<Infallible> for Error {
+        fn from(value: Infallible) -> Self {
+            match value {}
+        }
+    }
+
+    fn registry() -> Registry<VarInt<u16>, Message, Error> {
+        let mut registry = Registry::new();
+        registry
+            .register::<u32>(VarInt(1))
+            .register::<bool>(VarInt(300));
+        registry
+    }
+
+    #[test]
+    fn round_trip() {
+        let registry = registry();
+        let mut buf = BytesMut::new();
+        registry.encode(&0x1234u32, &mut buf).unwrap();
+        registry.encode(&true, &mut buf).unwrap();
+
+        let mut buf = buf.freeze();
+        assert_eq!(Message::Num(0x1234), registry.decode(&mut buf).unwrap());
+        assert_eq!(Message::Flag(true), registry.decode(&mut buf).unwrap());
+        assert!(!buf.has_remaining());
+    }
+
+    #[test]
+    fn wire_format() {
+        let registry = registry();
+        let mut buf = BytesMut::new();
+        registry.encode(&true, &mut buf).unwrap();
+        assert_eq!(&[0xac, 0x02, 0x01][..], &buf[..]);
+    }
+
+    #[test]
+    fn unknown_id() {
+        let registry = registry();
+        assert!(matches!(
+            registry.decode(&[0x02, 0x00][..]),
+            Err(BufTooShortOr::Or(RegistryDecodeError::UnknownId(
+                UnknownId(VarInt(2))
+            )))
+        ));
+    }
+
+    #[test]
+    fn inner_errors() {
+        let registry = registry();
+        assert!(matches!(
+            registry.decode(&[0xff, 0xff, 0xff][..]),
+            Err(BufTooShortOr::Or(RegistryDecodeError::Inner(Error::Id(_))))
+        ));
+        assert!(matches!(
+            registry.decode(&[0xac, 0x02, 0x02][..]),
+            Err(BufTooShortOr::Or(RegistryDecodeError::Inner(Error::Flag(
+                _
+            ))))
+        ));
+        assert!(matches!(
+            registry.decode(&[0x01, 0x00][..]),
+            Err(BufTooShortOr::TooShort)
+        ));
+    }
+
+    #[test]
+    fn unregistered_type() {
+        let registry = registry();
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            registry.encode(&1u8, &mut buf),
+            Err(BufTooShortOr::Or(RegistryEncodeError::Unregistered(_)))
+        ));
+        assert_eq!(0, buf.len());
+    }
+
+    #[test]
+    fn encode_too_short() {
+        let registry = registry();
+        // room for the ID, but not the value
+        let mut buf = StackBuf::<3>::new();
+        assert!(matches!(
+            registry.encode(&0x1234u32, &mut buf),
+            Err(BufTooShortOr::TooShort)
+        ));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "already registered"]
+    fn duplicate_id() {
+        let mut registry = registry();
+        registry.register_with::<u64>(VarInt(1), |_| Message::Num(0));
+    }
+
+    #[test]
+    #[should_panic = "already registered"]
+    fn duplicate_type() {
+        let mut registry = registry();
+        registry.register::<u32>(VarInt(2));
+    }
+
+    #[test]
+    fn type_erased() {
+        let mut registry = Registry::<u8, Box<dyn Any>, Infallible>::new();
+        registry
+            .register_with::<u32>(0, |v| Box::new(v))
+            .register_with::<u64>(1, |v| Box::new(v));
+
+        let mut buf = BytesMut::new();
+        registry.encode(&5u64, &mut buf).unwrap();
+        let message = registry.decode(buf.freeze()).unwrap();
+        assert_eq!(Some(&5u64), message.downcast_ref::<u64>());
+
+        let ids = [0u8, 1].into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [registry.id_of::<u32>(), registry.id_of::<u64>()]
+                .into_iter()
+                .map(|id| *id.unwrap())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,807 @@
+//! Machine-readable descriptions of the wire format of [`Encode`] and
+//! [`Decode`] types.
+//!
+//! When other programs which read and write the same bytes are written in
+//! different languages, the wire format has to be kept in sync by hand in each
+//! of them. Instead, types can implement [`Schema`] to describe their wire
+//! format as a [`SchemaType`], which can then be written out as JSON with
+//! [`SchemaType::to_json`]. A code generator for another language can read this
+//! JSON and emit a matching reader and writer.
+//!
+//! [`Schema`] is implemented for the types in this crate which have a
+//! structural encoding:
+//! * integers, floats, [`VarInt`]s, [`bool`], `NonZero*` and
+//!   `Option<NonZero*>`
+//! * [`char`] and [`Utf8Char`]
+//! * byte sequences, and byte array newtypes made with [`impl_byte_array!`]
+//! * the [`core::net`] address types
+//! * [`Duration`], [`Millis`] and [`Micros`], and `SystemTime` with the `std`
+//!   feature
+//! * [`Quantized`] and [`Fixed`], and `F16` and `Bf16` with the `half` feature
+//!
+//! Types whose wire format depends on runtime state, such as a [`Versioned`]
+//! body, a [`Registry`] message or a dynamic [`Value`], don't implement it.
+//! Neither do [`Checksummed`] and [`Compressed`], since [`SchemaType`] can't
+//! describe a checksum or compression algorithm.
+//!
+//! There is no derive macro for [`Schema`] yet, so for your own types,
+//! implement it by hand, describing each field in the order that they are
+//! encoded. This includes collections of your own types, which are usually
+//! encoded as a [`SchemaType::List`] or [`SchemaType::Array`].
+//!
+//! # Examples
+//!
+//! ```
+//! use octs::{
+//!     VarInt,
+//!     schema::{Schema, SchemaType},
+//! };
+//!
+//! struct Player {
+//!     id: VarInt<u32>,
+//!     health: u8,
+//!     name: Vec<u8>,
+//! }
+//!
+//! impl Schema for Player {
+//!     fn schema() -> SchemaType {
+//!         SchemaType::Struct {
+//!             name: "Player",
+//!             fields: vec![
+//!                 ("id", VarInt::<u32>::schema()),
+//!                 ("health", u8::schema()),
+//!                 ("name", Vec::<u8>::schema()),
+//!             ],
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     r#"{"type":"struct","name":"Player","fields":["#.to_owned()
+//!         + r#"{"name":"id","schema":{"type":"varint","signed":false,"bits":32}},"#
+//!         + r#"{"name":"health","schema":{"type":"int","signed":false,"bytes":1}},"#
+//!         + r#"{"name":"name","schema":{"type":"list","item":{"type":"int","signed":false,"bytes":1}}}"#
+//!         + "]}",
+//!     Player::schema().to_json(),
+//! );
+//! ```
+//!
+//! [`Encode`]: crate::Encode
+//! [`Decode`]: crate::Decode
+//! [`impl_byte_array!`]: crate::impl_byte_array
+//! [`Millis`]: crate::time::Millis
+//! [`Micros`]: crate::time::Micros
+//! [`Quantized`]: crate::quant::Quantized
+//! [`Fixed`]: crate::quant::Fixed
+//! [`Versioned`]: crate::version::Versioned
+//! [`Registry`]: crate::registry::Registry
+//! [`Value`]: crate::value::Value
+//! [`Checksummed`]: crate::checksum::Checksummed
+//! [`Compressed`]: crate::compress::Compressed
+
+use {
+    crate::{
+        Bytes, BytesMut, CanonicalVarInt, VarInt,
+        prim::Utf8Char,
+        quant::{Fixed, Quantized},
+        time::{Micros, Millis},
+    },
+    alloc::{boxed::Box, string::String, vec, vec::Vec},
+    core::{
+        fmt,
+        marker::{PhantomData, PhantomPinned},
+        mem::size_of,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+        num::{
+            NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroIsize, NonZeroU8, NonZeroU16,
+            NonZeroU32, NonZeroU64, NonZeroUsize,
+        },
+        time::Duration,
+    },
+};
+
+/// Type which can describe its wire format as a [`SchemaType`].
+///
+/// See the [module-level documentation](self).
+pub trait Schema {
+    /// Describes the wire format of this type.
+    fn schema() -> SchemaType;
+}
+
+/// Description of the wire format of a type.
+///
+/// See the [module-level documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SchemaType {
+    /// Encodes to no bytes.
+    Unit,
+    /// Single byte of `0` (false) or `1` (true).
+    Bool,
+    /// Big-endian integer, taking up `bytes` bytes.
+    Int {
+        /// Whether this is a two's complement signed integer.
+        signed: bool,
+        /// Number of bytes that this integer takes up.
+        bytes: usize,
+    },
+    /// Big-endian IEEE 754 floating-point number, taking up `bytes` bytes.
+    Float {
+        /// Number of bytes that this number takes up.
+        bytes: usize,
+    },
+    /// Big-endian bfloat16 number, which is the top 2 bytes of an IEEE 754
+    /// single-precision float.
+    Bf16,
+    /// Unicode scalar value as a big-endian [`u32`].
+    Char,
+    /// Unicode scalar value as its UTF-8 encoding, taking between 1 and 4
+    /// bytes.
+    Utf8Char,
+    /// Variable-length integer, in the format described in [`VarInt`].
+    VarInt {
+        /// Whether this integer is zigzag-encoded.
+        signed: bool,
+        /// Number of bits in the decoded integer.
+        bits: u32,
+    },
+    /// Same encoding as the inner schema, but a value of `0` is invalid.
+    NonZero(Box<Self>),
+    /// Same encoding as the inner schema, but a value of `0` represents no
+    /// value.
+    OptionNonZero(Box<Self>),
+    /// Fixed number of items, one after the other.
+    Array {
+        /// Schema of each item.
+        item: Box<Self>,
+        /// Number of items.
+        len: usize,
+    },
+    /// Number of items as a [`VarInt<usize>`], followed by that many items.
+    List(Box<Self>),
+    /// Floating-point value in the range `min..=max`, evenly divided into
+    /// `2^bits - 1` steps, encoded as the index of the step as a big-endian
+    /// unsigned integer in the minimum number of whole bytes.
+    Quantized {
+        /// Smallest value in the range.
+        min: i32,
+        /// Largest value in the range.
+        max: i32,
+        /// Number of bits in the step index.
+        bits: u32,
+    },
+    /// Floating-point value multiplied by `2^frac`, encoded as the integer
+    /// schema `int`.
+    Fixed {
+        /// Schema of the scaled integer.
+        int: Box<Self>,
+        /// Number of fractional bits.
+        frac: u32,
+    },
+    /// Each field, one after the other, in order.
+    Struct {
+        /// Name of this type.
+        name: &'static str,
+        /// Name and schema of each field.
+        fields: Vec<(&'static str, Self)>,
+    },
+    /// Tag, followed by the schema of the variant that the tag identifies.
+    Enum {
+        /// Name of this type.
+        name: &'static str,
+        /// Schema of the tag.
+        tag: Box<Self>,
+        /// Tag value, name and schema of each variant.
+        variants: Vec<(u64, &'static str, Self)>,
+    },
+}
+
+impl SchemaType {
+    /// Writes this schema as a JSON object into `f`.
+    ///
+    /// Each object has a `"type"` key holding the name of the variant in
+    /// `snake_case`, and the variant's fields as the other keys. Inner schemas
+    /// are written as nested objects under the `"inner"`, `"item"`, `"int"` or
+    /// `"tag"` keys, struct fields as an array of `{"name": ..., "schema": ...}`
+    /// objects, and enum variants as an array of
+    /// `{"tag": ..., "name": ..., "schema": ...}` objects.
+    ///
+    /// # Errors
+    ///
+    /// Errors if writing into `f` fails.
+    pub fn write_json(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Self::Unit => write!(f, r#"{{"type":"unit"}}"#),
+            Self::Bool => write!(f, r#"{{"type":"bool"}}"#),
+            Self::Int { signed, bytes } => {
+                write!(f, r#"{{"type":"int","signed":{signed},"bytes":{bytes}}}"#)
+            }
+            Self::Float { bytes } => write!(f, r#"{{"type":"float","bytes":{bytes}}}"#),
+            Self::Bf16 => write!(f, r#"{{"type":"bf16"}}"#),
+            Self::Char => write!(f, r#"{{"type":"char"}}"#),
+            Self::Utf8Char => write!(f, r#"{{"type":"utf8_char"}}"#),
+            Self::VarInt { signed, bits } => {
+                write!(f, r#"{{"type":"varint","signed":{signed},"bits":{bits}}}"#)
+            }
+            Self::NonZero(inner) => {
+                write!(f, r#"{{"type":"non_zero","inner":"#)?;
+                inner.write_json(f)?;
+                write!(f, "}}")
+            }
+            Self::OptionNonZero(inner) => {
+                write!(f, r#"{{"type":"option_non_zero","inner":"#)?;
+                inner.write_json(f)?;
+                write!(f, "}}")
+            }
+            Self::Array { item, len } => {
+                write!(f, r#"{{"type":"array","len":{len},"item":"#)?;
+                item.write_json(f)?;
+                write!(f, "}}")
+            }
+            Self::List(item) => {
+                write!(f, r#"{{"type":"list","item":"#)?;
+                item.write_json(f)?;
+                write!(f, "}}")
+            }
+            Self::Quantized { min, max, bits } => write!(
+                f,
+                r#"{{"type":"quantized","min":{min},"max":{max},"bits":{bits}}}"#
+            ),
+            Self::Fixed { int, frac } => {
+                write!(f, r#"{{"type":"fixed","frac":{frac},"int":"#)?;
+                int.write_json(f)?;
+                write!(f, "}}")
+            }
+            Self::Struct { name, fields } => {
+                write!(f, r#"{{"type":"struct","name":"#)?;
+                write_json_str(f, name)?;
+                write!(f, r#","fields":["#)?;
+                for (i, (name, schema)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, r#"{{"name":"#)?;
+                    write_json_str(f, name)?;
+                    write!(f, r#","schema":"#)?;
+                    schema.write_json(f)?;
+                    write!(f, "}}")?;
+                }
+                write!(f, "]}}")
+            }
+            Self::Enum {
+                name,
+                tag,
+                variants,
+            } => {
+                write!(f, r#"{{"type":"enum","name":"#)?;
+                write_json_str(f, name)?;
+                write!(f, r#","tag":"#)?;
+                tag.write_json(f)?;
+                write!(f, r#","variants":["#)?;
+                for (i, (tag, name, schema)) in variants.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, r#"{{"tag":{tag},"name":"#)?;
+                    write_json_str(f, name)?;
+                    write!(f, r#","schema":"#)?;
+                    schema.write_json(f)?;
+                    write!(f, "}}")?;
+                }
+                write!(f, "]}}")
+            }
+        }
+    }
+
+    /// Writes this schema as a JSON object into a [`String`].
+    ///
+    /// See [`SchemaType::write_json`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        // writing into a `String` never fails
+        _ = self.write_json(&mut json);
+        json
+    }
+}
+
+fn write_json_str(f: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str(r#"\""#)?,
+            '\\' => f.write_str(r"\\")?,
+            c if c.is_control() => write!(f, r"\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+macro_rules! impl_int {
+    ($signed:expr => $($ty:ty),*) => {
+        $(
+            impl Schema for $ty {
+                fn schema() -> SchemaType {
+                    SchemaType::Int {
+                        signed: $signed,
+                        bytes: size_of::<$ty>(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(false => usize, u8, u16, u32, u64);
+impl_int!(true => isize, i8, i16, i32, i64);
+#[cfg(feature = "i128")]
+impl_int!(false => u128);
+#[cfg(feature = "i128")]
+impl_int!(true => i128);
+
+macro_rules! impl_float {
+    ($($ty:ty),*) => {
+        $(
+            impl Schema for $ty {
+                fn schema() -> SchemaType {
+                    SchemaType::Float {
+                        bytes: size_of::<$ty>(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+#[cfg(feature = "half")]
+impl_float!(half::f16);
+
+#[cfg(feature = "half")]
+impl Schema for half::bf16 {
+    fn schema() -> SchemaType {
+        SchemaType::Bf16
+    }
+}
+
+#[cfg(feature = "half")]
+impl<F> Schema for crate::quant::F16<F> {
+    fn schema() -> SchemaType {
+        half::f16::schema()
+    }
+}
+
+#[cfg(feature = "half")]
+impl<F> Schema for crate::quant::Bf16<F> {
+    fn schema() -> SchemaType {
+        half::bf16::schema()
+    }
+}
+
+impl<F, const MIN: i32, const MAX: i32, const BITS: u32> Schema for Quantized<F, MIN, MAX, BITS> {
+    fn schema() -> SchemaType {
+        SchemaType::Quantized {
+            min: MIN,
+            max: MAX,
+            bits: BITS,
+        }
+    }
+}
+
+impl<F, I: Schema, const FRAC: u32> Schema for Fixed<F, I, FRAC> {
+    fn schema() -> SchemaType {
+        SchemaType::Fixed {
+            int: Box::new(I::schema()),
+            frac: FRAC,
+        }
+    }
+}
+
+macro_rules! impl_varint {
+    ($signed:expr => $($ty:ty),*) => {
+        $(
+            impl Schema for VarInt<$ty> {
+                fn schema() -> SchemaType {
+                    SchemaType::VarInt {
+                        signed: $signed,
+                        bits: <$ty>::BITS,
+                    }
+                }
+            }
+
+            // same wire format, but stricter decoding
+            impl Schema for CanonicalVarInt<$ty> {
+                fn schema() -> SchemaType {
+                    VarInt::<$ty>::schema()
+                }
+            }
+        )*
+    };
+}
+
+impl_varint!(false => usize, u8, u16, u32, u64);
+impl_varint!(true => isize, i8, i16, i32, i64);
+
+macro_rules! impl_nz {
+    ($($nz:ty => $base:ty),*) => {
+        $(
+            impl Schema for $nz {
+                fn schema() -> SchemaType {
+                    SchemaType::NonZero(Box::new(<$base>::schema()))
+                }
+            }
+
+            impl Schema for Option<$nz> {
+                fn schema() -> SchemaType {
+                    SchemaType::OptionNonZero(Box::new(<$base>::schema()))
+                }
+            }
+        )*
+    };
+}
+
+impl_nz!(
+    NonZeroUsize => usize,
+    NonZeroIsize => isize,
+    NonZeroU8 => u8,
+    NonZeroI8 => i8,
+    NonZeroU16 => u16,
+    NonZeroI16 => i16,
+    NonZeroU32 => u32,
+    NonZeroI32 => i32,
+    NonZeroU64 => u64,
+    NonZeroI64 => i64
+);
+#[cfg(feature = "i128")]
+impl_nz!(
+    core::num::NonZeroU128 => u128,
+    core::num::NonZeroI128 => i128
+);
+
+impl Schema for bool {
+    fn schema() -> SchemaType {
+        SchemaType::Bool
+    }
+}
+
+impl Schema for char {
+    fn schema() -> SchemaType {
+        SchemaType::Char
+    }
+}
+
+impl Schema for Utf8Char {
+    fn schema() -> SchemaType {
+        SchemaType::Utf8Char
+    }
+}
+
+impl Schema for () {
+    fn schema() -> SchemaType {
+        SchemaType::Unit
+    }
+}
+
+impl Schema for PhantomPinned {
+    fn schema() -> SchemaType {
+        SchemaType::Unit
+    }
+}
+
+impl<T: ?Sized> Schema for PhantomData<T> {
+    fn schema() -> SchemaType {
+        SchemaType::Unit
+    }
+}
+
+macro_rules! impl_bytes {
+    ($($ty:ty),*) => {
+        $(
+            impl Schema for $ty {
+                fn schema() -> SchemaType {
+                    SchemaType::List(Box::new(u8::schema()))
+                }
+            }
+        )*
+    };
+}
+
+impl_bytes!([u8], Vec<u8>, Bytes, BytesMut);
+
+// there's no `Encode` or `Decode` for `[T; N]`, so this isn't a `Schema` impl,
+// but it is used by types which are encoded as a fixed number of bytes
+#[doc(hidden)]
+#[must_use]
+pub fn __byte_array(len: usize) -> SchemaType {
+    SchemaType::Array {
+        item: Box::new(u8::schema()),
+        len,
+    }
+}
+
+impl Schema for Ipv4Addr {
+    fn schema() -> SchemaType {
+        __byte_array(4)
+    }
+}
+
+impl Schema for Ipv6Addr {
+    fn schema() -> SchemaType {
+        __byte_array(16)
+    }
+}
+
+impl Schema for IpAddr {
+    fn schema() -> SchemaType {
+        SchemaType::Enum {
+            name: "IpAddr",
+            tag: Box::new(u8::schema()),
+            variants: vec![(0, "V4", Ipv4Addr::schema()), (1, "V6", Ipv6Addr::schema())],
+        }
+    }
+}
+
+impl Schema for SocketAddrV4 {
+    fn schema() -> SchemaType {
+        SchemaType::Struct {
+            name: "SocketAddrV4",
+            fields: vec![("ip", Ipv4Addr::schema()), ("port", u16::schema())],
+        }
+    }
+}
+
+impl Schema for SocketAddrV6 {
+    fn schema() -> SchemaType {
+        SchemaType::Struct {
+            name: "SocketAddrV6",
+            fields: vec![("ip", Ipv6Addr::schema()), ("port", u16::schema())],
+        }
+    }
+}
+
+impl Schema for SocketAddr {
+    fn schema() -> SchemaType {
+        SchemaType::Enum {
+            name: "SocketAddr",
+            tag: Box::new(u8::schema()),
+            variants: vec![
+                (0, "V4", SocketAddrV4::schema()),
+                (1, "V6", SocketAddrV6::schema()),
+            ],
+        }
+    }
+}
+
+impl Schema for Duration {
+    fn schema() -> SchemaType {
+        SchemaType::Struct {
+            name: "Duration",
+            fields: vec![("secs", VarInt::<u64>::schema()), ("nanos", u32::schema())],
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Schema for std::time::SystemTime {
+    fn schema() -> SchemaType {
+        SchemaType::Struct {
+            name: "SystemTime",
+            fields: vec![("since_unix_epoch", Duration::schema())],
+        }
+    }
+}
+
+impl Schema for Millis {
+    fn schema() -> SchemaType {
+        SchemaType::Struct {
+            name: "Millis",
+            fields: vec![("millis", VarInt::<u64>::schema())],
+        }
+    }
+}
+
+impl Schema for Micros {
+    fn schema() -> SchemaType {
+        SchemaType::Struct {
+            name: "Micros",
+            fields: vec![("micros", VarInt::<u64>::schema())],
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Schema for uuid::Uuid {
+    fn schema() -> SchemaType {
+        __byte_array(16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{BufTooShortOr, Decode, Encode, Read, Write, test::round_trip},
+        core::convert::Infallible,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Packet {
+        id: VarInt<i32>,
+        flags: Option<NonZeroU16>,
+        payload: Vec<u8>,
+    }
+
+    impl Schema for Packet {
+        fn schema() -> SchemaType {
+            SchemaType::Struct {
+                name: "Packet",
+                fields: vec![
+                    ("id", VarInt::<i32>::schema()),
+                    ("flags", Option::<NonZeroU16>::schema()),
+                    ("payload", Vec::<u8>::schema()),
+                ],
+            }
+        }
+    }
+
+    impl crate::EncodeLen for Packet {
+        fn encode_len(&self) -> usize {
+            self.id.encode_len() + self.flags.encode_len() + self.payload.encode_len()
+        }
+    }
+
+    impl Encode for Packet {
+        type Error = Infallible;
+
+        fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+            dst.write(self.id)?;
+            dst.write(self.flags)?;
+            dst.write(&self.payload)?;
+            Ok(())
+        }
+    }
+
+    impl Decode for Packet {
+        type Error = crate::VarIntTooLarge;
+
+        fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+            Ok(Self {
+                id: src.read()?,
+                flags: src.read()?,
+                payload: src.read()?,
+            })
+        }
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(
+            SchemaType::Int {
+                signed: true,
+                bytes: 2
+            },
+            i16::schema()
+        );
+        assert_eq!(SchemaType::Float { bytes: 8 }, f64::schema());
+        assert_eq!(VarInt::<u64>::schema(), CanonicalVarInt::<u64>::schema());
+        assert_eq!(
+            SchemaType::NonZero(Box::new(u32::schema())),
+            NonZeroU32::schema()
+        );
+        assert_eq!(SchemaType::Unit, PhantomData::<str>::schema());
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            r#"{"type":"varint","signed":true,"bits":64}"#,
+            VarInt::<i64>::schema().to_json()
+        );
+        assert_eq!(
+            r#"{"type":"option_non_zero","inner":{"type":"int","signed":false,"bytes":1}}"#,
+            Option::<NonZeroU8>::schema().to_json()
+        );
+        assert_eq!(
+            concat!(
+                r#"{"type":"struct","name":"Packet","fields":["#,
+                r#"{"name":"id","schema":{"type":"varint","signed":true,"bits":32}},"#,
+                r#"{"name":"flags","schema":{"type":"option_non_zero","inner":{"type":"int","signed":false,"bytes":2}}},"#,
+                r#"{"name":"payload","schema":{"type":"list","item":{"type":"int","signed":false,"bytes":1}}}"#,
+                "]}",
+            ),
+            Packet::schema().to_json()
+        );
+    }
+
+    #[test]
+    fn crate_types() {
+        assert_eq!(
+            SchemaType::Array {
+                item: Box::new(u8::schema()),
+                len: 4
+            },
+            Ipv4Addr::schema()
+        );
+        assert_eq!(SchemaType::Char, char::schema());
+        assert_eq!(SchemaType::Utf8Char, Utf8Char::schema());
+        assert_eq!(
+            SchemaType::Struct {
+                name: "Duration",
+                fields: vec![("secs", VarInt::<u64>::schema()), ("nanos", u32::schema())],
+            },
+            Duration::schema()
+        );
+        assert_eq!(
+            SchemaType::Quantized {
+                min: -180,
+                max: 180,
+                bits: 16
+            },
+            Quantized::<f32, -180, 180, 16>::schema()
+        );
+        assert_eq!(
+            SchemaType::Fixed {
+                int: Box::new(i16::schema()),
+                frac: 8
+            },
+            Fixed::<f32, i16, 8>::schema()
+        );
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn half() {
+        assert_eq!(
+            SchemaType::Float { bytes: 2 },
+            crate::quant::F16::<f32>::schema()
+        );
+        assert_eq!(SchemaType::Bf16, crate::quant::Bf16::<f32>::schema());
+    }
+
+    #[test]
+    fn json_enum() {
+        assert_eq!(
+            concat!(
+                r#"{"type":"enum","name":"IpAddr","tag":{"type":"int","signed":false,"bytes":1},"#,
+                r#""variants":["#,
+                r#"{"tag":0,"name":"V4","schema":{"type":"array","len":4,"item":{"type":"int","signed":false,"bytes":1}}},"#,
+                r#"{"tag":1,"name":"V6","schema":{"type":"array","len":16,"item":{"type":"int","signed":false,"bytes":1}}}"#,
+                "]}",
+            ),
+            IpAddr::schema().to_json()
+        );
+        assert_eq!(
+            r#"{"type":"fixed","frac":4,"int":{"type":"int","signed":false,"bytes":1}}"#,
+            Fixed::<f64, u8, 4>::schema().to_json()
+        );
+        assert_eq!(
+            r#"{"type":"quantized","min":0,"max":1,"bits":8}"#,
+            Quantized::<f32, 0, 1, 8>::schema().to_json()
+        );
+    }
+
+    #[test]
+    fn json_escapes_names() {
+        let schema = SchemaType::Struct {
+            name: "a\"b\\c\n",
+            fields: vec![],
+        };
+        assert_eq!(
+            r#"{"type":"struct","name":"a\"b\\c\u000a","fields":[]}"#,
+            schema.to_json()
+        );
+    }
+
+    #[test]
+    fn schema_matches_encoding() {
+        // the schema says: zigzag varint, big-endian u16, varint length + bytes
+        let packet = Packet {
+            id: VarInt(-2),
+            flags: NonZeroU16::new(0x0102),
+            payload: vec![0xaa, 0xbb],
+        };
+        round_trip(&packet);
+        crate::assert_encodes!(packet, "03 0102 02 aabb");
+    }
+}
@@ -0,0 +1,262 @@
+use {
+    crate::{BufTooShortOr, Encode, FixedEncodeLen, Write},
+    bytes::{BufMut, buf::UninitSlice},
+    core::{fmt, ops::Deref},
+};
+
+/// Fixed-capacity buffer of `N` bytes which lives on the stack, and can be
+/// written into without allocating.
+///
+/// This implements [`BufMut`], so it can be used with [`Write`] just like a
+/// [`BytesMut`], except that it never grows - writing more than `N` bytes in
+/// total fails with [`BufTooShort`]. The written bytes can then be read back
+/// through [`Deref<Target = [u8]>`], and `&[u8]` implements [`Read`].
+///
+/// # Examples
+///
+/// ```
+/// use octs::{Read, StackBuf, VarInt, Write};
+///
+/// let mut buf = StackBuf::<8>::new();
+/// buf.write(0x1234u16).unwrap();
+/// buf.write(VarInt(300u32)).unwrap();
+/// assert_eq!(&[0x12, 0x34, 0xac, 0x02], &buf[..]);
+///
+/// // only 4 bytes are left
+/// buf.write(0u64).unwrap_err();
+///
+/// let mut src = &buf[..];
+/// assert_eq!(0x1234, src.read::<u16>().unwrap());
+/// assert_eq!(VarInt(300), src.read::<VarInt<u32>>().unwrap());
+/// ```
+///
+/// [`BytesMut`]: crate::BytesMut
+/// [`BufTooShort`]: crate::BufTooShort
+/// [`Read`]: crate::Read
+#[derive(Clone)]
+pub struct StackBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> StackBuf<N> {
+    /// Creates an empty buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Gets the maximum number of bytes that this buffer can hold, which is
+    /// always `N`.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Gets the number of bytes written into this buffer.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Gets if no bytes have been written into this buffer.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the bytes written into this buffer.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Discards all bytes written into this buffer, so that it can be written
+    /// into again from the start.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for StackBuf<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Debug for StackBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StackBuf").field(&self.as_slice()).finish()
+    }
+}
+
+impl<const N: usize> PartialEq for StackBuf<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for StackBuf<N> {}
+
+impl<const N: usize> Deref for StackBuf<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for StackBuf<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+// SAFETY: `chunk_mut` only ever exposes the unwritten, but already
+// initialized, tail of `buf`, and `advance_mut` never moves `len` past `N`.
+unsafe impl<const N: usize> BufMut for StackBuf<N> {
+    fn remaining_mut(&self) -> usize {
+        N - self.len
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining_mut(),
+            "cannot advance past `remaining_mut`: {cnt} > {}",
+            self.remaining_mut()
+        );
+        self.len += cnt;
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::new(&mut self.buf[self.len..])
+    }
+
+    fn put_slice(&mut self, src: &[u8]) {
+        assert!(
+            src.len() <= self.remaining_mut(),
+            "cannot put {} bytes into {} remaining",
+            src.len(),
+            self.remaining_mut()
+        );
+        self.buf[self.len..self.len + src.len()].copy_from_slice(src);
+        self.len += src.len();
+    }
+}
+
+/// Encodes `value` into an array of exactly [`FixedEncodeLen::ENCODE_LEN`]
+/// bytes, without allocating.
+///
+/// On stable Rust, the array's length can't be written as `T::ENCODE_LEN`, so
+/// it is a separate const parameter `N`, which is usually inferred from how the
+/// result is used. Using an `N` which is not equal to `T::ENCODE_LEN` fails to
+/// compile.
+///
+/// # Errors
+///
+/// Errors if `value` could not be encoded.
+///
+/// # Examples
+///
+/// ```
+/// use octs::encode_to_array;
+///
+/// let bytes: [u8; 4] = encode_to_array(&0x1234_5678u32).unwrap();
+/// assert_eq!([0x12, 0x34, 0x56, 0x78], bytes);
+/// ```
+///
+/// ```compile_fail
+/// // `u32` takes up 4 bytes, not 8
+/// let bytes: [u8; 8] = octs::encode_to_array(&0x1234_5678u32).unwrap();
+/// ```
+pub fn encode_to_array<T, const N: usize>(value: &T) -> Result<[u8; N], BufTooShortOr<T::Error>>
+where
+    T: Encode + FixedEncodeLen + ?Sized,
+{
+    const {
+        assert!(
+            N == T::ENCODE_LEN,
+            "array length must be equal to `T::ENCODE_LEN`"
+        );
+    }
+
+    let mut buf = [0; N];
+    let mut dst = &mut buf[..];
+    dst.write(value)?;
+    debug_assert_eq!(0, dst.len(), "`T` wrote fewer bytes than its `ENCODE_LEN`");
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{BufTooShort, Read, VarInt},
+        core::net::Ipv4Addr,
+    };
+
+    #[test]
+    fn write_and_read() {
+        let mut buf = StackBuf::<16>::new();
+        assert!(buf.is_empty());
+        assert_eq!(16, buf.capacity());
+
+        buf.write(true).unwrap();
+        buf.write(VarInt(u32::MAX)).unwrap();
+        buf.write_from(&[1u8, 2, 3][..]).unwrap();
+        buf.write_slice(&[0xabcdu16, 0xef01]).unwrap();
+        assert_eq!(13, buf.len());
+        assert_eq!(3, buf.remaining_mut());
+
+        let mut src = &buf[..];
+        assert!(src.read::<bool>().unwrap());
+        assert_eq!(VarInt(u32::MAX), src.read().unwrap());
+        assert_eq!([1, 2, 3], src.read_exact::<3>().unwrap());
+        let mut values = [0u16; 2];
+        src.read_into(&mut values).unwrap();
+        assert_eq!([0xabcd, 0xef01], values);
+        assert_eq!(0, src.len());
+    }
+
+    #[test]
+    fn full() {
+        let mut buf = StackBuf::<3>::new();
+        buf.write(0x1234u16).unwrap();
+        assert_eq!(
+            Err(BufTooShort),
+            buf.write(0x5678u16).map_err(BufTooShort::from)
+        );
+        // a failed write leaves the buffer untouched
+        assert_eq!(&[0x12, 0x34], &buf[..]);
+        buf.write(0xffu8).unwrap();
+        assert_eq!(0, buf.remaining_mut());
+
+        buf.clear();
+        assert!(buf.is_empty());
+        buf.write(0x5678u16).unwrap();
+        assert_eq!(&[0x56, 0x78], &buf[..]);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut buf = StackBuf::<0>::new();
+        buf.write(()).unwrap();
+        buf.write(0u8).unwrap_err();
+        assert_eq!(StackBuf::new(), buf);
+    }
+
+    #[test]
+    fn to_array() {
+        let bytes: [u8; 2] = encode_to_array(&0xbeefu16).unwrap();
+        assert_eq!([0xbe, 0xef], bytes);
+
+        let bytes = encode_to_array::<_, 4>(&Ipv4Addr::new(192, 168, 0, 1)).unwrap();
+        assert_eq!([192, 168, 0, 1], bytes);
+
+        let bytes: [u8; 0] = encode_to_array(&()).unwrap();
+        assert_eq!([0u8; 0], bytes);
+    }
+}
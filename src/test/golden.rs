@@ -0,0 +1,284 @@
+use {
+    super::encode,
+    crate::Encode,
+    alloc::{string::String, vec::Vec},
+    core::fmt::{Debug, Write as _},
+};
+
+/// Parses a string of hex bytes, such as `"01 ff 80 01"`, into bytes.
+///
+/// All whitespace is ignored, so bytes may be grouped in any way.
+///
+/// # Panics
+///
+/// Panics if `hex` contains non-hex characters, or an odd number of hex
+/// digits.
+#[must_use]
+pub fn parse_hex(hex: &str) -> Vec<u8> {
+    let digits = hex
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            let digit = c
+                .to_digit(16)
+                .unwrap_or_else(|| panic!("invalid hex digit {c:?} in {hex:?}"));
+            #[allow(clippy::cast_possible_truncation)]
+            let digit = digit as u8;
+            digit
+        })
+        .collect::<Vec<_>>();
+    assert!(digits.len() % 2 == 0, "odd number of hex digits in {hex:?}");
+    digits
+        .chunks_exact(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect()
+}
+
+/// Formats bytes as lowercase space-separated hex, with `per_line` bytes on
+/// each line.
+///
+/// # Panics
+///
+/// Panics if `per_line` is 0.
+#[must_use]
+pub fn format_hex(bytes: &[u8], per_line: usize) -> String {
+    assert!(per_line > 0, "cannot format hex with 0 bytes per line");
+    let mut out = String::new();
+    for line in bytes.chunks(per_line) {
+        for (i, byte) in line.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            _ = write!(out, "{byte:02x}");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders `expected` and `actual` next to each other as hex, with lines that
+/// differ marked with a `!`.
+#[must_use]
+pub fn hex_diff(expected: &[u8], actual: &[u8]) -> String {
+    const PER_LINE: usize = 8;
+    const COLUMN_WIDTH: usize = PER_LINE * 3 - 1;
+
+    fn line(bytes: &[u8], start: usize) -> String {
+        let end = (start + PER_LINE).min(bytes.len());
+        let line = bytes.get(start..end).unwrap_or_default();
+        format_hex(line, PER_LINE).trim_end().into()
+    }
+
+    let mut out = String::new();
+    _ = writeln!(out, "  offset | {:<COLUMN_WIDTH$} | actual", "expected");
+    let len = expected.len().max(actual.len());
+    for start in (0..len).step_by(PER_LINE) {
+        let expected = line(expected, start);
+        let actual = line(actual, start);
+        let marker = if expected == actual { ' ' } else { '!' };
+        _ = writeln!(
+            out,
+            "{marker} {start:06x} | {expected:<COLUMN_WIDTH$} | {actual}"
+        );
+    }
+    out
+}
+
+fn check(expected: &[u8], actual: &[u8]) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(alloc::format!(
+            "encoded bytes do not match\nexpected len = {}, actual len = {}\n{}",
+            expected.len(),
+            actual.len(),
+            hex_diff(expected, actual)
+        ))
+    }
+}
+
+/// Asserts that `value` encodes into exactly the bytes given as a hex string.
+///
+/// `hex` is parsed using [`parse_hex`]. On mismatch, this panics with a
+/// side-by-side hexdump of the expected and actual bytes.
+///
+/// Prefer using [`assert_encodes`] instead.
+///
+/// [`assert_encodes`]: crate::assert_encodes
+#[track_caller]
+pub fn encodes_hex<T>(value: &T, hex: &str)
+where
+    T: Encode,
+    <T as Encode>::Error: Debug,
+{
+    let actual = encode(value);
+    if let Err(msg) = check(&parse_hex(hex), &actual) {
+        panic!("{msg}");
+    }
+}
+
+/// Asserts that a value encodes into exactly the bytes given as a hex string.
+///
+/// On mismatch, this panics with a side-by-side hexdump of the expected and
+/// actual bytes. See [`encodes_hex`].
+///
+/// # Examples
+///
+/// ```
+/// # use octs::{assert_encodes, VarInt};
+/// assert_encodes!(VarInt(300u32), "ac 02");
+/// assert_encodes!(0x1234u16, "1234");
+/// ```
+///
+/// [`encodes_hex`]: crate::test::encodes_hex
+#[macro_export]
+macro_rules! assert_encodes {
+    ($value:expr, $hex:expr $(,)?) => {
+        $crate::test::encodes_hex(&$value, $hex)
+    };
+}
+
+/// Name of the environment variable which, when set to a non-empty value,
+/// makes [`snapshot`] overwrite snapshot files instead of comparing against
+/// them.
+#[cfg(feature = "std")]
+pub const UPDATE_SNAPSHOTS_VAR: &str = "OCTS_UPDATE_SNAPSHOTS";
+
+/// Asserts that `value` encodes into the bytes stored in the hex snapshot file
+/// at `path`.
+///
+/// The snapshot file contains the expected bytes formatted by [`format_hex`],
+/// and is parsed with [`parse_hex`]. Commit these files alongside your tests,
+/// so that any change to your wire format shows up as a failing test.
+///
+/// When the [`UPDATE_SNAPSHOTS_VAR`] environment variable is set to a
+/// non-empty value, the snapshot file is created or overwritten with the
+/// actual encoded bytes instead, and the assertion always passes.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use octs::{test::snapshot, VarInt};
+/// snapshot(
+///     &VarInt(300u32),
+///     concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots/varint_300.hex"),
+/// );
+/// ```
+#[cfg(feature = "std")]
+#[track_caller]
+pub fn snapshot<T>(value: &T, path: impl AsRef<std::path::Path>)
+where
+    T: Encode,
+    <T as Encode>::Error: Debug,
+{
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some_and(|v| !v.is_empty());
+    if let Err(msg) = check_snapshot(&encode(value), path.as_ref(), update) {
+        panic!("{msg}");
+    }
+}
+
+#[cfg(feature = "std")]
+fn check_snapshot(actual: &[u8], path: &std::path::Path, update: bool) -> Result<(), String> {
+    use std::{fs, io::ErrorKind};
+
+    let path_str = path.display();
+    if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| alloc::format!("failed to create {}: {err}", parent.display()))?;
+        }
+        fs::write(path, format_hex(actual, 16))
+            .map_err(|err| alloc::format!("failed to write {path_str}: {err}"))?;
+        return Ok(());
+    }
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(alloc::format!(
+                "snapshot {path_str} does not exist\nrun with \
+                 {UPDATE_SNAPSHOTS_VAR}=1 to create it\nactual bytes:\n{}",
+                format_hex(actual, 16)
+            ));
+        }
+        Err(err) => return Err(alloc::format!("failed to read {path_str}: {err}")),
+    };
+    check(&parse_hex(&expected), actual).map_err(|msg| {
+        alloc::format!(
+            "snapshot {path_str} does not match\nrun with {UPDATE_SNAPSHOTS_VAR}=1 to update \
+             it\n{msg}"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::VarInt};
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            alloc::vec![0x01, 0xff, 0x80, 0x01],
+            parse_hex("01 ff 80 01")
+        );
+        assert_eq!(alloc::vec![0x01, 0xff, 0x80, 0x01], parse_hex("01ff\n8001"));
+        assert_eq!(alloc::vec![0xab], parse_hex("AB"));
+        assert_eq!(alloc::vec![0u8; 0], parse_hex(""));
+    }
+
+    #[test]
+    #[should_panic = "odd number"]
+    fn parse_odd() {
+        _ = parse_hex("012");
+    }
+
+    #[test]
+    #[should_panic = "invalid hex digit"]
+    fn parse_invalid() {
+        _ = parse_hex("0g");
+    }
+
+    #[test]
+    #[should_panic = "0 bytes per line"]
+    fn format_zero_per_line() {
+        _ = format_hex(&[0x01], 0);
+    }
+
+    #[test]
+    fn encodes() {
+        crate::assert_encodes!(VarInt(300u32), "ac 02");
+        crate::assert_encodes!(true, "01");
+        crate::assert_encodes!((), "");
+    }
+
+    #[test]
+    #[should_panic = "encoded bytes do not match"]
+    fn encodes_mismatch() {
+        crate::assert_encodes!(VarInt(300u32), "ac 03");
+    }
+
+    #[test]
+    fn diff() {
+        let diff = hex_diff(&[1, 2, 3, 4, 5, 6, 7, 8, 9], &[1, 2, 3, 4, 5, 6, 7, 8, 10]);
+        let mut lines = diff.lines();
+        lines.next().unwrap();
+        assert!(lines.next().unwrap().starts_with("  000000"));
+        assert!(lines.next().unwrap().starts_with("! 000008 | 09"));
+        assert!(lines.next().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn snapshot_file() {
+        let dir = std::env::temp_dir().join(alloc::format!("octs-snapshot-{}", std::process::id()));
+        let path = dir.join("nested").join("varint.hex");
+        let bytes = [0xac, 0x02];
+
+        check_snapshot(&bytes, &path, false).unwrap_err();
+        check_snapshot(&bytes, &path, true).unwrap();
+        check_snapshot(&bytes, &path, false).unwrap();
+        check_snapshot(&[0xac, 0x03], &path, false).unwrap_err();
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,308 @@
+#![allow(clippy::missing_panics_doc)]
+
+//! Utilities for testing trait implementations.
+//!
+//! Use these functions when writing unit tests for your [`Encode`] and
+//! [`Decode`] implementations.
+//!
+//! Property-based variants of these functions, which test many generated
+//! values instead of a single hand-picked value, are available in:
+//! * `proptest` - requires the `proptest` feature
+//! * `quickcheck` - requires the `quickcheck` feature
+//!
+//! Since you can't trust your inputs, you should also test how your [`Decode`]
+//! implementations behave on malformed input, using [`truncated`] and
+//! [`byte_flips`]. To go further, use [`decode_fuzz_target`] to fuzz your
+//! implementation with [`cargo fuzz`].
+//!
+//! To catch accidental changes to your wire format, compare the encoded form
+//! of your values against known bytes using `assert_encodes` (requires the
+//! `alloc` feature) or `snapshot` (requires the `std` feature).
+//!
+//! Most of these functions encode into a [`BytesMut`]. To test without
+//! allocating, use [`round_trip_in`] to encode into a buffer that you provide,
+//! such as a [`StackBuf`] or an array.
+//!
+//! [`decode_fuzz_target`]: crate::decode_fuzz_target
+//! [`cargo fuzz`]: https://github.com/rust-fuzz/cargo-fuzz
+//! [`StackBuf`]: crate::StackBuf
+
+#[cfg(feature = "alloc")]
+mod golden;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "quickcheck")]
+pub mod quickcheck;
+
+#[cfg(feature = "alloc")]
+pub use golden::*;
+use {
+    crate::{
+        Buf, BufTooShortOr, Bytes, BytesMut, Decode, Encode, EncodeLen, FixedEncodeLenHint, Read,
+        Write, inspect::hexdump,
+    },
+    core::fmt::Debug,
+};
+
+/// Asserts that `T`'s [`EncodeLen::encode_len`] is within the bounds of
+/// [`FixedEncodeLenHint`].
+pub fn encode_len_hint<T: FixedEncodeLenHint>(value: &T) {
+    let encode_len = value.encode_len();
+    assert!(
+        encode_len >= T::MIN_ENCODE_LEN,
+        "encode_len = {encode_len}, T::MIN_ENCODE_LEN: {}",
+        T::MIN_ENCODE_LEN
+    );
+    assert!(
+        encode_len <= T::MAX_ENCODE_LEN,
+        "encode_len = {encode_len}, T::MAX_ENCODE_LEN: {}",
+        T::MAX_ENCODE_LEN
+    );
+}
+
+/// Asserts that `decode(encode(value)) == value`.
+///
+/// This encodes into a [`BytesMut`]. To test without allocating, use
+/// [`round_trip_in`].
+pub fn round_trip<T>(value: &T)
+where
+    T: Debug + Encode + Decode + EncodeLen + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    let encode_len = value.encode_len();
+
+    let mut buf = BytesMut::with_capacity(encode_len);
+    buf.write(&value).unwrap();
+    assert_eq!(encode_len, buf.len());
+
+    let mut buf = buf.freeze();
+    assert_eq!(encode_len, buf.remaining());
+
+    let buf_clone = buf.clone();
+    let actual = buf.read::<T>().unwrap();
+    assert!(
+        *value == actual,
+        "expected = {value:?}, actual = {actual:?}, buf:\n{}",
+        hexdump(buf_clone)
+    );
+    assert_eq!(0, buf.remaining());
+}
+
+/// Asserts that `decode(encode(value)) == value`, encoding into `buf` instead
+/// of allocating.
+///
+/// `buf` must be at least as long as `value`'s [`EncodeLen::encode_len`].
+///
+/// # Examples
+///
+/// ```
+/// # use octs::{test::round_trip_in, VarInt};
+/// round_trip_in(&VarInt(300u32), &mut [0u8; 8]);
+/// ```
+pub fn round_trip_in<T>(value: &T, buf: &mut [u8])
+where
+    T: Debug + Encode + Decode + EncodeLen + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    let encode_len = value.encode_len();
+    assert!(
+        buf.len() >= encode_len,
+        "buf.len() = {}, encode_len = {encode_len}",
+        buf.len()
+    );
+
+    let len = buf.len();
+    let mut dst = &mut buf[..];
+    dst.write(value).unwrap();
+    let written = len - dst.len();
+    assert_eq!(encode_len, written);
+
+    let encoded = &buf[..written];
+    let mut src = encoded;
+    let actual = src.read::<T>().unwrap();
+    assert!(
+        *value == actual,
+        "expected = {value:?}, actual = {actual:?}, buf:\n{}",
+        hexdump(encoded)
+    );
+    assert_eq!(0, src.len());
+}
+
+/// Asserts [`encode_len_hint`] and [`round_trip`] for `T`.
+pub fn hint_round_trip<T>(value: &T)
+where
+    T: Debug + Encode + Decode + EncodeLen + FixedEncodeLenHint + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    encode_len_hint(value);
+    round_trip(value);
+}
+
+fn encode<T>(value: &T) -> Bytes
+where
+    T: Encode,
+    <T as Encode>::Error: Debug,
+{
+    let mut buf = BytesMut::new();
+    buf.write(value).unwrap();
+    buf.freeze()
+}
+
+/// Asserts that decoding any truncated form of `encode(value)` fails with
+/// [`BufTooShortOr::TooShort`].
+///
+/// This checks every strict prefix of the encoded bytes, from empty up to one
+/// byte short of the full encoding. Decoding must not succeed, and must not
+/// fail with any other error, since the bytes which were present were valid.
+pub fn truncated<T>(value: &T)
+where
+    T: Debug + Encode + Decode,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    let buf = encode(value);
+    for len in 0..buf.len() {
+        let prefix = buf.slice(..len);
+        match prefix.clone().read::<T>() {
+            Err(BufTooShortOr::TooShort) => {}
+            result => panic!(
+                "expected TooShort, actual = {result:?}, buf: {:?}, prefix: {:?}",
+                buf.chunk(),
+                prefix.chunk()
+            ),
+        }
+    }
+}
+
+/// Asserts that decoding `encode(value)` with corrupted bytes never panics.
+///
+/// This decodes the encoded bytes with:
+/// * every single bit flipped, one at a time
+/// * many random sets of bytes overwritten with random values
+///
+/// The result of decoding is ignored - it may succeed or fail - but it must
+/// not panic, or consume more bytes than were available. The random
+/// corruptions use a fixed seed, so failures are reproducible.
+pub fn byte_flips<T>(value: &T)
+where
+    T: Encode + Decode,
+    <T as Encode>::Error: Debug,
+{
+    const RANDOM_ITERS: usize = 256;
+
+    let buf = encode(value);
+    if buf.is_empty() {
+        return;
+    }
+
+    for index in 0..buf.len() {
+        for bit in 0..8 {
+            let mut corrupt = BytesMut::from(&buf[..]);
+            corrupt[index] ^= 1 << bit;
+            fuzz_decode::<T>(&corrupt);
+        }
+    }
+
+    // xorshift64
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    for _ in 0..RANDOM_ITERS {
+        let mut corrupt = BytesMut::from(&buf[..]);
+        #[allow(clippy::cast_possible_truncation)]
+        for _ in 0..=(next() as usize % corrupt.len()) {
+            let index = next() as usize % corrupt.len();
+            corrupt[index] = next() as u8;
+        }
+        fuzz_decode::<T>(&corrupt);
+    }
+}
+
+/// Attempts to decode a `T` from arbitrary bytes, ignoring the result.
+///
+/// The bytes are decoded both as a contiguous buffer, and as a buffer split
+/// into two chunks, to exercise any special handling of non-contiguous
+/// buffers. This is the function that [`decode_fuzz_target`] calls on each
+/// fuzzer input.
+///
+/// [`decode_fuzz_target`]: crate::decode_fuzz_target
+pub fn fuzz_decode<T: Decode>(data: &[u8]) {
+    let mut contiguous = data;
+    _ = contiguous.read::<T>();
+
+    let (front, back) = data.split_at(data.len() / 2);
+    let mut chained = front.chain(back);
+    _ = chained.read::<T>();
+}
+
+/// Creates a [`cargo fuzz`] target which decodes a value of the given type
+/// from the fuzzer's input, using [`fuzz_decode`].
+///
+/// This must be used in a `fuzz_targets` binary which depends on
+/// [`libfuzzer-sys`], as set up by `cargo fuzz init`.
+///
+/// # Examples
+///
+/// In `fuzz/fuzz_targets/decode_packet.rs`:
+///
+/// ```ignore
+/// #![no_main]
+///
+/// octs::decode_fuzz_target!(my_crate::Packet);
+/// ```
+///
+/// Then run `cargo fuzz run decode_packet`.
+///
+/// [`cargo fuzz`]: https://github.com/rust-fuzz/cargo-fuzz
+/// [`fuzz_decode`]: crate::test::fuzz_decode
+/// [`libfuzzer-sys`]: https://docs.rs/libfuzzer-sys
+#[macro_export]
+macro_rules! decode_fuzz_target {
+    ($ty:ty) => {
+        ::libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+            $crate::test::fuzz_decode::<$ty>(data);
+        });
+    };
+}
+
+/// Gets the unsigned values at which the encoded length of a [`VarInt`] of a
+/// `bits`-wide integer changes, plus the minimum and maximum values.
+///
+/// [`VarInt`]: crate::VarInt
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+fn varint_boundaries(bits: u32) -> alloc::vec::Vec<u64> {
+    let max = u64::MAX >> (u64::BITS - bits);
+    let mut values = alloc::vec![0, 1, max - 1, max];
+    let mut shift = 7;
+    while shift < bits {
+        let boundary = 1u64 << shift;
+        values.extend([boundary - 1, boundary]);
+        shift += 7;
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::VarInt};
+
+    #[test]
+    fn round_trip_in_buf() {
+        round_trip_in(&VarInt(u64::MAX), &mut [0u8; 10]);
+        // extra space is left untouched
+        round_trip_in(&0x1234u16, &mut [0u8; 64]);
+    }
+
+    #[test]
+    #[should_panic = "encode_len"]
+    fn round_trip_in_too_short() {
+        round_trip_in(&0x1234_5678u32, &mut [0u8; 3]);
+    }
+}
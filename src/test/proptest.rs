@@ -0,0 +1,225 @@
+//! Property-based testing utilities using [`proptest`].
+//!
+//! Each function here generates many values of a type, either using its
+//! [`Arbitrary`] implementation or a user-provided [`Strategy`], and runs the
+//! equivalent function in the [parent module](super) on each of them. If any
+//! value fails, it is shrunk to a minimal failing value and the test panics.
+//!
+//! This module also implements [`Arbitrary`] for [`VarInt`]s, which
+//! deliberately generates values on either side of every boundary where the
+//! encoded length of the varint changes, alongside uniformly random values.
+
+use {
+    super::varint_boundaries,
+    crate::{Decode, Encode, EncodeLen, FixedEncodeLenHint, VarInt},
+    ::proptest::{
+        arbitrary::{Arbitrary, any},
+        prop_oneof, sample,
+        strategy::{BoxedStrategy, Strategy},
+        test_runner::TestRunner,
+    },
+    alloc::vec::Vec,
+    core::fmt::Debug,
+};
+
+fn run<S: Strategy>(strategy: &S, test: impl Fn(&S::Value)) {
+    let mut runner = TestRunner::default();
+    if let Err(err) = runner.run(strategy, |value| {
+        test(&value);
+        Ok(())
+    }) {
+        panic!("{err}");
+    }
+}
+
+/// Asserts [`encode_len_hint`] for values generated by `strategy`.
+///
+/// [`encode_len_hint`]: super::encode_len_hint
+pub fn encode_len_hint_with<S>(strategy: &S)
+where
+    S: Strategy,
+    S::Value: FixedEncodeLenHint,
+{
+    run(strategy, super::encode_len_hint);
+}
+
+/// Asserts [`encode_len_hint`] for arbitrary values of `T`.
+///
+/// [`encode_len_hint`]: super::encode_len_hint
+pub fn encode_len_hint<T>()
+where
+    T: Arbitrary + FixedEncodeLenHint,
+{
+    encode_len_hint_with(&any::<T>());
+}
+
+/// Asserts [`round_trip`] for values generated by `strategy`.
+///
+/// [`round_trip`]: super::round_trip
+pub fn round_trip_with<S>(strategy: &S)
+where
+    S: Strategy,
+    S::Value: Debug + Encode + Decode + EncodeLen + PartialEq,
+    <S::Value as Encode>::Error: Debug,
+    <S::Value as Decode>::Error: Debug,
+{
+    run(strategy, super::round_trip);
+}
+
+/// Asserts [`round_trip`] for arbitrary values of `T`.
+///
+/// [`round_trip`]: super::round_trip
+pub fn round_trip<T>()
+where
+    T: Arbitrary + Debug + Encode + Decode + EncodeLen + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    round_trip_with(&any::<T>());
+}
+
+/// Asserts [`hint_round_trip`] for values generated by `strategy`.
+///
+/// [`hint_round_trip`]: super::hint_round_trip
+pub fn hint_round_trip_with<S>(strategy: &S)
+where
+    S: Strategy,
+    S::Value: Debug + Encode + Decode + EncodeLen + FixedEncodeLenHint + PartialEq,
+    <S::Value as Encode>::Error: Debug,
+    <S::Value as Decode>::Error: Debug,
+{
+    run(strategy, super::hint_round_trip);
+}
+
+/// Asserts [`hint_round_trip`] for arbitrary values of `T`.
+///
+/// [`hint_round_trip`]: super::hint_round_trip
+pub fn hint_round_trip<T>()
+where
+    T: Arbitrary + Debug + Encode + Decode + EncodeLen + FixedEncodeLenHint + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    hint_round_trip_with(&any::<T>());
+}
+
+macro_rules! impl_unsigned {
+    ($ty:ty) => {
+        impl Arbitrary for VarInt<$ty> {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            #[allow(clippy::cast_possible_truncation)]
+            fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                let boundaries = varint_boundaries(<$ty>::BITS)
+                    .into_iter()
+                    .map(|v| v as $ty)
+                    .collect::<Vec<_>>();
+                prop_oneof![sample::select(boundaries), any::<$ty>()]
+                    .prop_map(VarInt)
+                    .boxed()
+            }
+        }
+    };
+}
+
+impl_unsigned!(usize);
+impl_unsigned!(u8);
+impl_unsigned!(u16);
+impl_unsigned!(u32);
+impl_unsigned!(u64);
+
+macro_rules! impl_signed {
+    ($ty:ty, $un:ty) => {
+        impl Arbitrary for VarInt<$ty> {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            #[allow(clippy::cast_possible_truncation)]
+            fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                let boundaries = varint_boundaries(<$ty>::BITS)
+                    .into_iter()
+                    .map(|v| VarInt::<$ty>::zigzag_decode(v as $un))
+                    .collect::<Vec<_>>();
+                prop_oneof![sample::select(boundaries), any::<$ty>()]
+                    .prop_map(VarInt)
+                    .boxed()
+            }
+        }
+    };
+}
+
+impl_signed!(isize, usize);
+impl_signed!(i8, u8);
+impl_signed!(i16, u16);
+impl_signed!(i32, u32);
+impl_signed!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use {super::*, core::num::NonZeroU32};
+
+    #[test]
+    fn prims() {
+        hint_round_trip::<bool>();
+        hint_round_trip::<u64>();
+        hint_round_trip::<i16>();
+        hint_round_trip::<Option<NonZeroU32>>();
+    }
+
+    #[test]
+    fn varints() {
+        hint_round_trip::<VarInt<usize>>();
+        hint_round_trip::<VarInt<u8>>();
+        hint_round_trip::<VarInt<u16>>();
+        hint_round_trip::<VarInt<u32>>();
+        hint_round_trip::<VarInt<u64>>();
+        hint_round_trip::<VarInt<isize>>();
+        hint_round_trip::<VarInt<i8>>();
+        hint_round_trip::<VarInt<i16>>();
+        hint_round_trip::<VarInt<i32>>();
+        hint_round_trip::<VarInt<i64>>();
+    }
+
+    #[test]
+    fn custom_strategy() {
+        round_trip_with(&(0u32..10).prop_map(VarInt));
+    }
+
+    #[test]
+    #[should_panic = "Test failed"]
+    fn detects_failure() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Lossy(u16);
+
+        impl Encode for Lossy {
+            type Error = core::convert::Infallible;
+
+            fn encode(
+                &self,
+                mut dst: impl crate::Write,
+            ) -> Result<(), crate::BufTooShortOr<Self::Error>> {
+                #[allow(clippy::cast_possible_truncation)]
+                dst.write(self.0 as u8)
+            }
+        }
+
+        impl EncodeLen for Lossy {
+            fn encode_len(&self) -> usize {
+                1
+            }
+        }
+
+        impl Decode for Lossy {
+            type Error = core::convert::Infallible;
+
+            fn decode(
+                mut src: impl crate::Read,
+            ) -> Result<Self, crate::BufTooShortOr<Self::Error>> {
+                Ok(Self(u16::from(src.read::<u8>()?)))
+            }
+        }
+
+        round_trip_with(&any::<u16>().prop_map(Lossy));
+    }
+}
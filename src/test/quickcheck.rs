@@ -0,0 +1,161 @@
+//! Property-based testing utilities using [`quickcheck`].
+//!
+//! Each function here generates many values of a type using its
+//! [`Arbitrary`] implementation, and runs the equivalent function in the
+//! [parent module](super) on each of them. If any value fails, it is shrunk to
+//! a minimal failing value and the test panics.
+//!
+//! This module also implements [`Arbitrary`] for [`VarInt`]s, which
+//! deliberately generates values on either side of every boundary where the
+//! encoded length of the varint changes, alongside random values.
+
+// quickcheck properties must take their inputs by value
+#![allow(clippy::needless_pass_by_value)]
+
+use {
+    super::varint_boundaries,
+    crate::{Decode, Encode, EncodeLen, FixedEncodeLenHint, VarInt},
+    ::quickcheck::{Arbitrary, Gen, QuickCheck},
+    alloc::boxed::Box,
+    core::fmt::Debug,
+};
+
+/// Asserts [`encode_len_hint`] for arbitrary values of `T`.
+///
+/// [`encode_len_hint`]: super::encode_len_hint
+pub fn encode_len_hint<T>()
+where
+    T: Arbitrary + Debug + FixedEncodeLenHint,
+{
+    fn prop<T: FixedEncodeLenHint>(value: T) -> bool {
+        super::encode_len_hint(&value);
+        true
+    }
+
+    QuickCheck::new().quickcheck(prop::<T> as fn(T) -> bool);
+}
+
+/// Asserts [`round_trip`] for arbitrary values of `T`.
+///
+/// [`round_trip`]: super::round_trip
+pub fn round_trip<T>()
+where
+    T: Arbitrary + Debug + Encode + Decode + EncodeLen + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    fn prop<T>(value: T) -> bool
+    where
+        T: Debug + Encode + Decode + EncodeLen + PartialEq,
+        <T as Encode>::Error: Debug,
+        <T as Decode>::Error: Debug,
+    {
+        super::round_trip(&value);
+        true
+    }
+
+    QuickCheck::new().quickcheck(prop::<T> as fn(T) -> bool);
+}
+
+/// Asserts [`hint_round_trip`] for arbitrary values of `T`.
+///
+/// [`hint_round_trip`]: super::hint_round_trip
+pub fn hint_round_trip<T>()
+where
+    T: Arbitrary + Debug + Encode + Decode + EncodeLen + FixedEncodeLenHint + PartialEq,
+    <T as Encode>::Error: Debug,
+    <T as Decode>::Error: Debug,
+{
+    fn prop<T>(value: T) -> bool
+    where
+        T: Debug + Encode + Decode + EncodeLen + FixedEncodeLenHint + PartialEq,
+        <T as Encode>::Error: Debug,
+        <T as Decode>::Error: Debug,
+    {
+        super::hint_round_trip(&value);
+        true
+    }
+
+    QuickCheck::new().quickcheck(prop::<T> as fn(T) -> bool);
+}
+
+macro_rules! impl_unsigned {
+    ($ty:ty) => {
+        impl Arbitrary for VarInt<$ty> {
+            #[allow(clippy::cast_possible_truncation)]
+            fn arbitrary(g: &mut Gen) -> Self {
+                if bool::arbitrary(g) {
+                    let boundaries = varint_boundaries(<$ty>::BITS);
+                    let v = *g.choose(&boundaries).expect("should not be empty");
+                    VarInt(v as $ty)
+                } else {
+                    VarInt(<$ty>::arbitrary(g))
+                }
+            }
+
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                Box::new(self.0.shrink().map(VarInt))
+            }
+        }
+    };
+}
+
+impl_unsigned!(usize);
+impl_unsigned!(u8);
+impl_unsigned!(u16);
+impl_unsigned!(u32);
+impl_unsigned!(u64);
+
+macro_rules! impl_signed {
+    ($ty:ty, $un:ty) => {
+        impl Arbitrary for VarInt<$ty> {
+            #[allow(clippy::cast_possible_truncation)]
+            fn arbitrary(g: &mut Gen) -> Self {
+                if bool::arbitrary(g) {
+                    let boundaries = varint_boundaries(<$ty>::BITS);
+                    let v = *g.choose(&boundaries).expect("should not be empty");
+                    VarInt(VarInt::<$ty>::zigzag_decode(v as $un))
+                } else {
+                    VarInt(<$ty>::arbitrary(g))
+                }
+            }
+
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                Box::new(self.0.shrink().map(VarInt))
+            }
+        }
+    };
+}
+
+impl_signed!(isize, usize);
+impl_signed!(i8, u8);
+impl_signed!(i16, u16);
+impl_signed!(i32, u32);
+impl_signed!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use {super::*, core::num::NonZeroU32};
+
+    #[test]
+    fn prims() {
+        hint_round_trip::<bool>();
+        hint_round_trip::<u64>();
+        hint_round_trip::<i16>();
+        hint_round_trip::<Option<NonZeroU32>>();
+    }
+
+    #[test]
+    fn varints() {
+        hint_round_trip::<VarInt<usize>>();
+        hint_round_trip::<VarInt<u8>>();
+        hint_round_trip::<VarInt<u16>>();
+        hint_round_trip::<VarInt<u32>>();
+        hint_round_trip::<VarInt<u64>>();
+        hint_round_trip::<VarInt<isize>>();
+        hint_round_trip::<VarInt<i8>>();
+        hint_round_trip::<VarInt<i16>>();
+        hint_round_trip::<VarInt<i32>>();
+        hint_round_trip::<VarInt<i64>>();
+    }
+}
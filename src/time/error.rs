@@ -0,0 +1,76 @@
+use {
+    crate::{BufError, VarIntTooLarge, prim::InvalidValue},
+    core::{error::Error, fmt::Display},
+};
+
+/// Error when decoding a [`Duration`], or a type which is encoded as one.
+///
+/// [`Duration`]: core::time::Duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationError {
+    /// Number of seconds was too large to fit into a [`u64`].
+    TooLarge(VarIntTooLarge),
+    /// Number of nanoseconds was not less than one second, or the resulting
+    /// time could not be represented.
+    Invalid(InvalidValue),
+}
+
+impl Display for DurationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLarge(err) => write!(f, "{err}"),
+            Self::Invalid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl BufError for DurationError {}
+
+impl Error for DurationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TooLarge(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+        }
+    }
+}
+
+/// Attempted to encode a [`Millis`] or [`Micros`], but the duration in that
+/// unit was too large to fit into a [`u64`].
+///
+/// [`Millis`]: crate::time::Millis
+/// [`Micros`]: crate::time::Micros
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DurationTooLong;
+
+impl Display for DurationTooLong {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "duration too long")
+    }
+}
+
+impl BufError for DurationTooLong {}
+
+impl Error for DurationTooLong {}
+
+/// Attempted to encode a [`SystemTime`] which is earlier than the
+/// [`UNIX_EPOCH`].
+///
+/// [`SystemTime`]: std::time::SystemTime
+/// [`UNIX_EPOCH`]: std::time::UNIX_EPOCH
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BeforeEpoch;
+
+#[cfg(feature = "std")]
+impl Display for BeforeEpoch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "time is before the unix epoch")
+    }
+}
+
+#[cfg(feature = "std")]
+impl BufError for BeforeEpoch {}
+
+#[cfg(feature = "std")]
+impl Error for BeforeEpoch {}
@@ -0,0 +1,219 @@
+//! Encodings for durations and points in time.
+//!
+//! [`Duration`] is encoded with full nanosecond precision as:
+//! * seconds as a [`VarInt<u64>`]
+//! * nanoseconds as a [`u32`], which must be less than one second
+//!
+//! With the `std` feature, `SystemTime` is encoded as the [`Duration`] since
+//! the UNIX epoch. Times before the epoch can't be encoded.
+//!
+//! When you don't need nanosecond precision, such as for latency or ping
+//! measurements, use [`Millis`] or [`Micros`] to encode a [`Duration`] as a
+//! single [`VarInt<u64>`] in that unit instead.
+//!
+//! [`VarInt<u64>`]: crate::VarInt
+
+mod error;
+#[cfg(feature = "std")]
+mod system;
+
+pub use error::*;
+use {
+    crate::{
+        BufTooShort, BufTooShortOr, Decode, Encode, EncodeLen, FixedEncodeLenHint, Read, VarInt,
+        Write, prim::InvalidValue,
+    },
+    core::{convert::Infallible, time::Duration},
+};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl EncodeLen for Duration {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt(self.as_secs()).encode_len() + self.subsec_nanos().encode_len()
+    }
+}
+
+impl FixedEncodeLenHint for Duration {
+    const MIN_ENCODE_LEN: usize = VarInt::<u64>::MIN_ENCODE_LEN + 4;
+
+    const MAX_ENCODE_LEN: usize = VarInt::<u64>::MAX_ENCODE_LEN + 4;
+}
+
+impl Decode for Duration {
+    type Error = DurationError;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let VarInt(secs) = src
+            .read::<VarInt<u64>>()
+            .map_err(|e| e.map_or(DurationError::TooLarge))?;
+        let nanos = src.read::<u32>().map_err(BufTooShort::from)?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(BufTooShortOr::Or(DurationError::Invalid(InvalidValue(()))));
+        }
+        Ok(Self::new(secs, nanos))
+    }
+}
+
+impl Encode for Duration {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        if dst.remaining_mut() < self.encode_len() {
+            return Err(BufTooShortOr::TooShort);
+        }
+        dst.write(VarInt(self.as_secs()))?;
+        dst.write(self.subsec_nanos())
+    }
+}
+
+macro_rules! impl_unit {
+    ($name:ident, $unit:literal, $as_unit:ident, $from_unit:ident) => {
+        #[doc = concat!("[`Duration`] which is encoded as a [`VarInt<u64>`] number of ", $unit, ".")]
+        ///
+        #[doc = concat!("Any precision finer than one of ", $unit, " is truncated on encode.")]
+        /// Durations too long to fit into a [`u64`] in this unit fail to encode
+        /// with [`DurationTooLong`].
+        ///
+        /// [`VarInt<u64>`]: crate::VarInt
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $name(pub Duration);
+
+        impl From<Duration> for $name {
+            fn from(value: Duration) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Duration {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl $name {
+            fn to_varint(self) -> Option<VarInt<u64>> {
+                u64::try_from(self.0.$as_unit()).ok().map(VarInt)
+            }
+        }
+
+        impl EncodeLen for $name {
+            #[inline]
+            fn encode_len(&self) -> usize {
+                // out of range durations fail to encode anyway, but this must
+                // still stay within the bounds of `FixedEncodeLenHint`
+                self.to_varint()
+                    .map_or(VarInt::<u64>::MAX_ENCODE_LEN, |v| v.encode_len())
+            }
+        }
+
+        impl FixedEncodeLenHint for $name {
+            const MIN_ENCODE_LEN: usize = VarInt::<u64>::MIN_ENCODE_LEN;
+
+            const MAX_ENCODE_LEN: usize = VarInt::<u64>::MAX_ENCODE_LEN;
+        }
+
+        impl Decode for $name {
+            type Error = crate::VarIntTooLarge;
+
+            #[inline]
+            fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                let VarInt(n) = src.read()?;
+                Ok(Self(Duration::$from_unit(n)))
+            }
+        }
+
+        impl Encode for $name {
+            type Error = DurationTooLong;
+
+            #[inline]
+            fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+                let n = self.to_varint().ok_or(DurationTooLong)?;
+                dst.write(n)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_unit!(Millis, "milliseconds", as_millis, from_millis);
+impl_unit!(Micros, "microseconds", as_micros, from_micros);
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    const DURATIONS: [Duration; 5] = [
+        Duration::ZERO,
+        Duration::from_nanos(1),
+        Duration::from_millis(1500),
+        Duration::new(u32::MAX as u64, NANOS_PER_SEC - 1),
+        Duration::MAX,
+    ];
+
+    #[test]
+    fn round_trip_duration() {
+        for d in DURATIONS {
+            hint_round_trip(&d);
+        }
+    }
+
+    #[test]
+    fn round_trip_units() {
+        hint_round_trip(&Millis(Duration::ZERO));
+        hint_round_trip(&Millis(Duration::from_millis(250)));
+        hint_round_trip(&Millis(Duration::from_millis(u64::MAX)));
+        hint_round_trip(&Micros(Duration::from_micros(16_667)));
+        hint_round_trip(&Micros(Duration::from_micros(u64::MAX)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(Duration::from_millis(1500), "01 1d cd 65 00");
+        crate::assert_encodes!(Millis(Duration::from_millis(300)), "ac 02");
+    }
+
+    #[test]
+    fn units_truncate() {
+        let mut buf = crate::BytesMut::new();
+        buf.write(Millis(Duration::from_micros(1999))).unwrap();
+        assert_eq!(
+            Duration::from_millis(1),
+            buf.freeze().read::<Millis>().unwrap().0
+        );
+    }
+
+    #[test]
+    fn encode_too_long() {
+        let mut buf = crate::BytesMut::new();
+        assert!(matches!(
+            buf.write(Micros(Duration::MAX)),
+            Err(BufTooShortOr::Or(DurationTooLong))
+        ));
+        assert!(buf.is_empty());
+
+        encode_len_hint(&Millis(Duration::MAX));
+        encode_len_hint(&Micros(Duration::MAX));
+    }
+
+    #[test]
+    fn decode_invalid_nanos() {
+        assert!(matches!(
+            (&[0x00, 0x3b, 0x9a, 0xca, 0x00][..]).read::<Duration>(),
+            Err(BufTooShortOr::Or(DurationError::Invalid(_)))
+        ));
+    }
+
+    #[test]
+    fn decode_malformed() {
+        for d in DURATIONS {
+            truncated(&d);
+            byte_flips(&d);
+        }
+        truncated(&Millis(Duration::from_millis(u64::MAX)));
+    }
+}
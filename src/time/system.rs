@@ -0,0 +1,78 @@
+use {
+    super::{BeforeEpoch, DurationError},
+    crate::{
+        BufTooShortOr, Decode, Encode, EncodeLen, FixedEncodeLenHint, Read, Write,
+        prim::InvalidValue,
+    },
+    core::time::Duration,
+    std::time::{SystemTime, UNIX_EPOCH},
+};
+
+impl EncodeLen for SystemTime {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        self.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .encode_len()
+    }
+}
+
+impl FixedEncodeLenHint for SystemTime {
+    const MIN_ENCODE_LEN: usize = Duration::MIN_ENCODE_LEN;
+
+    const MAX_ENCODE_LEN: usize = Duration::MAX_ENCODE_LEN;
+}
+
+impl Decode for SystemTime {
+    type Error = DurationError;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let since_epoch = src.read::<Duration>()?;
+        UNIX_EPOCH
+            .checked_add(since_epoch)
+            .ok_or(BufTooShortOr::Or(DurationError::Invalid(InvalidValue(()))))
+    }
+}
+
+impl Encode for SystemTime {
+    type Error = BeforeEpoch;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        let since_epoch = self.duration_since(UNIX_EPOCH).map_err(|_| BeforeEpoch)?;
+        dst.write(since_epoch)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    #[test]
+    fn round_trip() {
+        hint_round_trip(&UNIX_EPOCH);
+        hint_round_trip(&(UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789)));
+        hint_round_trip(&SystemTime::now());
+    }
+
+    #[test]
+    fn encode_before_epoch() {
+        let mut buf = crate::BytesMut::new();
+        assert!(matches!(
+            buf.write(UNIX_EPOCH - Duration::from_secs(1)),
+            Err(BufTooShortOr::Or(BeforeEpoch))
+        ));
+    }
+
+    #[test]
+    fn decode_out_of_range() {
+        let mut buf = crate::BytesMut::new();
+        buf.write(Duration::MAX).unwrap();
+        assert!(matches!(
+            buf.freeze().read::<SystemTime>(),
+            Err(BufTooShortOr::Or(DurationError::Invalid(_)))
+        ));
+    }
+}
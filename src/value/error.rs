@@ -0,0 +1,57 @@
+use {
+    crate::{BufError, VarIntTooLarge, prim::InvalidValue},
+    core::{error::Error, fmt::Display},
+};
+
+/// Attempted to decode a [`Value`] which was nested more than [`MAX_DEPTH`]
+/// levels deep.
+///
+/// [`Value`]: crate::value::Value
+/// [`MAX_DEPTH`]: crate::value::MAX_DEPTH
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TooDeep;
+
+impl Display for TooDeep {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "value nested too deeply")
+    }
+}
+
+impl BufError for TooDeep {}
+
+impl Error for TooDeep {}
+
+/// Error when decoding a [`Value`].
+///
+/// [`Value`]: crate::value::Value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueError {
+    /// See [`VarIntTooLarge`].
+    TooLarge(VarIntTooLarge),
+    /// See [`InvalidValue`].
+    InvalidValue(InvalidValue),
+    /// See [`TooDeep`].
+    TooDeep(TooDeep),
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLarge(err) => write!(f, "{err}"),
+            Self::InvalidValue(err) => write!(f, "{err}"),
+            Self::TooDeep(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl BufError for ValueError {}
+
+impl Error for ValueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TooLarge(err) => Some(err),
+            Self::InvalidValue(err) => Some(err),
+            Self::TooDeep(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,400 @@
+//! Self-describing value which can be decoded without knowing its type at
+//! compile time.
+//!
+//! Usually, to decode bytes you need to know the type which was encoded into
+//! them. However, tools like loggers, proxies, and admin consoles may want to
+//! inspect and re-encode payloads without knowing their types. [`Value`] is a
+//! dynamically-typed tree of primitives, lists, and maps which encodes the type
+//! of each node alongside its data, so it can always be decoded back.
+//!
+//! Note that this is a *different* wire format to the one used by the
+//! statically-typed [`Encode`] and [`Decode`] impls - a `Value::UInt(5)` is not
+//! encoded the same way as a `5u64`.
+//!
+//! With the `serde` feature, any type implementing `Serialize` can be converted
+//! into a [`Value`] using `to_value`, and back using `from_value`.
+//!
+//! # Wire format
+//!
+//! * tag as a [`u8`]
+//! * payload, depending on the tag:
+//!
+//! | Tag | Variant           | Payload                                            |
+//! |-----|-------------------|----------------------------------------------------|
+//! | `0` | [`Value::Unit`]   | nothing                                            |
+//! | `1` | [`Value::Bool`]   | [`bool`]                                           |
+//! | `2` | [`Value::Int`]    | [`VarInt<i64>`]                                    |
+//! | `3` | [`Value::UInt`]   | [`VarInt<u64>`]                                    |
+//! | `4` | [`Value::Float`]  | [`f64`]                                            |
+//! | `5` | [`Value::Bytes`]  | length as a [`VarInt<usize>`], then the bytes      |
+//! | `6` | [`Value::String`] | length as a [`VarInt<usize>`], then UTF-8 bytes    |
+//! | `7` | [`Value::List`]   | length as a [`VarInt<usize>`], then each value     |
+//! | `8` | [`Value::Map`]    | length as a [`VarInt<usize>`], then each key-value |
+//!
+//! # Examples
+//!
+//! ```
+//! use octs::{BytesMut, Read, Write, value::Value};
+//!
+//! let value = Value::Map(vec![
+//!     (Value::from("name"), Value::from("octs")),
+//!     (
+//!         Value::from("versions"),
+//!         Value::List(vec![Value::from(1u32), Value::from(2u32)]),
+//!     ),
+//! ]);
+//!
+//! let mut buf = BytesMut::new();
+//! buf.write(&value).unwrap();
+//! assert_eq!(value, buf.freeze().read::<Value>().unwrap());
+//! ```
+
+mod error;
+#[cfg(feature = "serde")]
+mod serde;
+
+pub use error::*;
+#[cfg(feature = "serde")]
+pub use serde::*;
+use {
+    crate::{
+        BufTooShort, BufTooShortOr, Bytes, Decode, Encode, EncodeLen, Read, VarInt, VarIntTooLarge,
+        Write, prim::InvalidValue,
+    },
+    alloc::{string::String, vec::Vec},
+    core::convert::Infallible,
+};
+
+/// Maximum number of levels that a [`Value`] may be nested when decoding.
+///
+/// Without this limit, a malicious input of many nested lists could overflow
+/// the stack while decoding. A value which is nested deeper than this fails to
+/// decode with [`TooDeep`].
+pub const MAX_DEPTH: usize = 64;
+
+/// Dynamically-typed value with a self-describing encoding.
+///
+/// See the [module-level documentation](self).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Value {
+    /// Absence of a value.
+    #[default]
+    Unit,
+    /// Boolean.
+    Bool(bool),
+    /// Signed integer.
+    Int(i64),
+    /// Unsigned integer.
+    UInt(u64),
+    /// Floating-point number.
+    Float(f64),
+    /// Sequence of raw bytes.
+    Bytes(Bytes),
+    /// UTF-8 string.
+    String(String),
+    /// Sequence of values.
+    List(Vec<Self>),
+    /// Sequence of key-value pairs, in the order that they were inserted.
+    ///
+    /// Keys are not required to be unique.
+    Map(Vec<(Self, Self)>),
+}
+
+const TAG_UNIT: u8 = 0;
+const TAG_BOOL: u8 = 1;
+const TAG_INT: u8 = 2;
+const TAG_UINT: u8 = 3;
+const TAG_FLOAT: u8 = 4;
+const TAG_BYTES: u8 = 5;
+const TAG_STRING: u8 = 6;
+const TAG_LIST: u8 = 7;
+const TAG_MAP: u8 = 8;
+
+impl Value {
+    const fn tag(&self) -> u8 {
+        match self {
+            Self::Unit => TAG_UNIT,
+            Self::Bool(_) => TAG_BOOL,
+            Self::Int(_) => TAG_INT,
+            Self::UInt(_) => TAG_UINT,
+            Self::Float(_) => TAG_FLOAT,
+            Self::Bytes(_) => TAG_BYTES,
+            Self::String(_) => TAG_STRING,
+            Self::List(_) => TAG_LIST,
+            Self::Map(_) => TAG_MAP,
+        }
+    }
+
+    fn encode_unchecked(&self, dst: &mut impl Write) -> Result<(), BufTooShort> {
+        dst.write(self.tag())?;
+        match self {
+            Self::Unit => {}
+            Self::Bool(v) => dst.write(v)?,
+            Self::Int(v) => dst.write(VarInt(*v))?,
+            Self::UInt(v) => dst.write(VarInt(*v))?,
+            Self::Float(v) => dst.write(v)?,
+            Self::Bytes(v) => dst.write(v)?,
+            Self::String(v) => dst.write(v.as_bytes())?,
+            Self::List(values) => {
+                dst.write(VarInt(values.len()))?;
+                for value in values {
+                    value.encode_unchecked(dst)?;
+                }
+            }
+            Self::Map(entries) => {
+                dst.write(VarInt(entries.len()))?;
+                for (key, value) in entries {
+                    key.encode_unchecked(dst)?;
+                    value.encode_unchecked(dst)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_depth(src: &mut impl Read, depth: usize) -> Result<Self, BufTooShortOr<ValueError>> {
+        if depth > MAX_DEPTH {
+            return Err(BufTooShortOr::Or(ValueError::TooDeep(TooDeep)));
+        }
+
+        let tag = src.read::<u8>()?;
+        Ok(match tag {
+            TAG_UNIT => Self::Unit,
+            TAG_BOOL => Self::Bool(
+                src.read()
+                    .map_err(|err| err.map_or(ValueError::InvalidValue))?,
+            ),
+            TAG_INT => Self::Int(read_varint(src)?),
+            TAG_UINT => Self::UInt(read_varint(src)?),
+            TAG_FLOAT => Self::Float(src.read()?),
+            TAG_BYTES => Self::Bytes(src.read().map_err(|err| err.map_or(ValueError::TooLarge))?),
+            TAG_STRING => {
+                let bytes = src
+                    .read::<Vec<u8>>()
+                    .map_err(|err| err.map_or(ValueError::TooLarge))?;
+                Self::String(String::from_utf8(bytes).map_err(|_| invalid())?)
+            }
+            TAG_LIST => {
+                let len = read_varint::<usize>(src)?;
+                // every value takes at least 1 byte, so don't trust `len` to
+                // pre-allocate more than what's remaining
+                let mut values = Vec::with_capacity(len.min(src.remaining()));
+                for _ in 0..len {
+                    values.push(Self::decode_depth(src, depth + 1)?);
+                }
+                Self::List(values)
+            }
+            TAG_MAP => {
+                let len = read_varint::<usize>(src)?;
+                let mut entries = Vec::with_capacity(len.min(src.remaining() / 2));
+                for _ in 0..len {
+                    let key = Self::decode_depth(src, depth + 1)?;
+                    let value = Self::decode_depth(src, depth + 1)?;
+                    entries.push((key, value));
+                }
+                Self::Map(entries)
+            }
+            _ => return Err(invalid()),
+        })
+    }
+}
+
+fn read_varint<T>(src: &mut impl Read) -> Result<T, BufTooShortOr<ValueError>>
+where
+    VarInt<T>: Decode<Error = VarIntTooLarge>,
+{
+    src.read::<VarInt<T>>()
+        .map(|VarInt(v)| v)
+        .map_err(|err| err.map_or(ValueError::TooLarge))
+}
+
+const fn invalid() -> BufTooShortOr<ValueError> {
+    BufTooShortOr::Or(ValueError::InvalidValue(InvalidValue(())))
+}
+
+impl EncodeLen for Value {
+    fn encode_len(&self) -> usize {
+        1 + match self {
+            Self::Unit => 0,
+            Self::Bool(v) => v.encode_len(),
+            Self::Int(v) => VarInt(*v).encode_len(),
+            Self::UInt(v) => VarInt(*v).encode_len(),
+            Self::Float(v) => v.encode_len(),
+            Self::Bytes(v) => v.encode_len(),
+            Self::String(v) => v.as_bytes().encode_len(),
+            Self::List(values) => {
+                VarInt(values.len()).encode_len()
+                    + values.iter().map(EncodeLen::encode_len).sum::<usize>()
+            }
+            Self::Map(entries) => {
+                VarInt(entries.len()).encode_len()
+                    + entries
+                        .iter()
+                        .map(|(key, value)| key.encode_len() + value.encode_len())
+                        .sum::<usize>()
+            }
+        }
+    }
+}
+
+impl Encode for Value {
+    type Error = Infallible;
+
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        // check once up front, so that a nested value doesn't fail to encode
+        // halfway through
+        if dst.remaining_mut() < self.encode_len() {
+            return Err(BufTooShortOr::TooShort);
+        }
+        Ok(self.encode_unchecked(&mut dst)?)
+    }
+}
+
+impl Decode for Value {
+    type Error = ValueError;
+
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        Self::decode_depth(&mut src, 0)
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($inner:ty) <- $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::$variant(<$inner>::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Bool(bool) <- bool);
+impl_from!(Int(i64) <- i8, i16, i32, i64);
+impl_from!(UInt(u64) <- u8, u16, u32, u64);
+impl_from!(Float(f64) <- f32, f64);
+impl_from!(Bytes(Bytes) <- Bytes, Vec<u8>);
+impl_from!(String(String) <- String, &str);
+impl_from!(List(Vec<Value>) <- Vec<Value>);
+
+impl From<()> for Value {
+    #[inline]
+    fn from((): ()) -> Self {
+        Self::Unit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{BytesMut, test::round_trip},
+        alloc::vec,
+    };
+
+    fn sample() -> Value {
+        Value::Map(vec![
+            (Value::from("unit"), Value::Unit),
+            (Value::from("bool"), Value::from(true)),
+            (Value::from("int"), Value::from(-300i32)),
+            (Value::from("uint"), Value::from(u64::MAX)),
+            (Value::from("float"), Value::from(1.5f64)),
+            (Value::from("bytes"), Value::from(vec![1u8, 2, 3])),
+            (
+                Value::from("list"),
+                Value::List(vec![Value::from("a"), Value::List(vec![])]),
+            ),
+            (Value::from(1u8), Value::Map(vec![])),
+        ])
+    }
+
+    fn decode(mut src: &[u8]) -> Result<Value, BufTooShortOr<ValueError>> {
+        src.read()
+    }
+
+    #[test]
+    fn round_trip_values() {
+        round_trip(&Value::Unit);
+        round_trip(&Value::from(false));
+        round_trip(&Value::from(i64::MIN));
+        round_trip(&Value::from(0u8));
+        round_trip(&Value::from(f64::INFINITY));
+        round_trip(&Value::from(""));
+        round_trip(&sample());
+    }
+
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(Value::Unit, "00");
+        crate::assert_encodes!(Value::from(true), "01 01");
+        crate::assert_encodes!(Value::from(-1i8), "02 01");
+        crate::assert_encodes!(Value::from(300u16), "03 ac02");
+        crate::assert_encodes!(Value::from("hi"), "06 02 6869");
+        crate::assert_encodes!(
+            Value::List(vec![Value::Unit, Value::from(1u8)]),
+            "07 02 00 0301"
+        );
+        crate::assert_encodes!(Value::Map(vec![(Value::Unit, Value::Unit)]), "08 01 00 00");
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            decode(&[9]),
+            Err(BufTooShortOr::Or(ValueError::InvalidValue(_)))
+        ));
+        assert!(matches!(
+            decode(&[TAG_BOOL, 2]),
+            Err(BufTooShortOr::Or(ValueError::InvalidValue(_)))
+        ));
+        assert!(matches!(
+            decode(&[TAG_STRING, 1, 0xff]),
+            Err(BufTooShortOr::Or(ValueError::InvalidValue(_)))
+        ));
+        assert!(matches!(
+            decode(&[
+                TAG_UINT, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01
+            ]),
+            Err(BufTooShortOr::Or(ValueError::TooLarge(_)))
+        ));
+        // claims many elements, but the buffer runs out
+        assert!(matches!(
+            decode(&[TAG_LIST, 0xff, 0xff, 0xff, 0xff, 0x0f, TAG_UNIT]),
+            Err(BufTooShortOr::TooShort)
+        ));
+    }
+
+    #[test]
+    fn depth_limit() {
+        let nested = |depth: usize| {
+            let mut bytes = vec![];
+            for _ in 0..depth {
+                bytes.extend_from_slice(&[TAG_LIST, 1]);
+            }
+            bytes.push(TAG_UNIT);
+            bytes
+        };
+
+        decode(&nested(MAX_DEPTH)).unwrap();
+        assert!(matches!(
+            decode(&nested(MAX_DEPTH + 1)),
+            Err(BufTooShortOr::Or(ValueError::TooDeep(_)))
+        ));
+    }
+
+    #[test]
+    fn encode_too_short() {
+        let value = sample();
+        let mut buf = vec![0u8; value.encode_len() - 1];
+        let mut dst = &mut buf[..];
+        dst.write(&value).unwrap_err();
+        // nothing was written
+        assert_eq!(value.encode_len() - 1, dst.len());
+
+        let mut buf = BytesMut::new();
+        buf.write(&value).unwrap();
+        assert_eq!(value.encode_len(), buf.len());
+    }
+}
@@ -0,0 +1,787 @@
+use {
+    super::Value,
+    crate::Bytes,
+    alloc::{
+        borrow::ToOwned,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    },
+    core::{
+        error::Error,
+        fmt::{self, Display},
+    },
+    serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{
+            self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+            SeqAccess, Unexpected, VariantAccess, Visitor,
+            value::{MapDeserializer, SeqDeserializer},
+        },
+        forward_to_deserialize_any, ser,
+    },
+};
+
+/// Error when converting between a [`Value`] and a type implementing `serde`'s
+/// traits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SerdeError(String);
+
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl de::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Converts a `T` into a [`Value`].
+///
+/// Values are mapped in the same way as most self-describing formats, such as
+/// JSON:
+/// * structs and maps become [`Value::Map`]s, with field names as
+///   [`Value::String`] keys
+/// * sequences and tuples become [`Value::List`]s
+/// * `None`, `()` and unit structs become [`Value::Unit`]
+/// * `Some(x)` becomes a [`Value::List`] containing only `x`, so that `Some(())`
+///   and nested options like `Some(None)` can be told apart from `None`
+/// * enum variants are externally tagged - a unit variant becomes its name as
+///   a [`Value::String`], and any other variant becomes a [`Value::Map`] with a
+///   single entry, mapping its name to its contents
+///
+/// # Errors
+///
+/// Errors if `T`'s [`Serialize`] impl fails, or if it serializes a 128-bit
+/// integer.
+///
+/// # Examples
+///
+/// ```
+/// use octs::value::{Value, from_value, to_value};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Player {
+///     name: String,
+///     health: u8,
+/// }
+///
+/// let player = Player {
+///     name: "alice".into(),
+///     health: 100,
+/// };
+/// let value = to_value(&player).unwrap();
+/// assert_eq!(
+///     Value::Map(vec![
+///         (Value::from("name"), Value::from("alice")),
+///         (Value::from("health"), Value::from(100u8)),
+///     ]),
+///     value,
+/// );
+/// assert_eq!(player, from_value::<Player>(value).unwrap());
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, SerdeError> {
+    value.serialize(ValueSerializer)
+}
+
+/// Converts a [`Value`] into a `T`.
+///
+/// See [`to_value`] for how types are mapped.
+///
+/// # Errors
+///
+/// Errors if `value` does not have the shape that `T` expects.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, SerdeError> {
+    T::deserialize(value)
+}
+
+impl Value {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Self::Unit => Unexpected::Unit,
+            Self::Bool(v) => Unexpected::Bool(*v),
+            Self::Int(v) => Unexpected::Signed(*v),
+            Self::UInt(v) => Unexpected::Unsigned(*v),
+            Self::Float(v) => Unexpected::Float(*v),
+            Self::Bytes(v) => Unexpected::Bytes(v),
+            Self::String(v) => Unexpected::Str(v),
+            Self::List(_) => Unexpected::Seq,
+            Self::Map(_) => Unexpected::Map,
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unit => serializer.serialize_unit(),
+            Self::Bool(v) => serializer.serialize_bool(*v),
+            Self::Int(v) => serializer.serialize_i64(*v),
+            Self::UInt(v) => serializer.serialize_u64(*v),
+            Self::Float(v) => serializer.serialize_f64(*v),
+            Self::Bytes(v) => serializer.serialize_bytes(v),
+            Self::String(v) => serializer.serialize_str(v),
+            Self::List(values) => serializer.collect_seq(values),
+            Self::Map(entries) => serializer.collect_map(entries.iter().map(|(k, v)| (k, v))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Value::Int(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Value::UInt(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(Bytes::copy_from_slice(v)))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Value::Bytes(Bytes::from(v)))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        Ok(Value::List(vec![Value::deserialize(deserializer)?]))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::List(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Value::Map(entries))
+    }
+}
+
+// serializer
+
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeVariant<SerializeList>;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeVariant<SerializeMap>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerdeError> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerdeError> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerdeError> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerdeError> {
+        Ok(Value::Bytes(Bytes::copy_from_slice(v)))
+    }
+
+    fn serialize_none(self) -> Result<Value, SerdeError> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, SerdeError> {
+        Ok(Value::List(vec![value.serialize(self)?]))
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerdeError> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerdeError> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerdeError> {
+        Ok(Value::from(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, SerdeError> {
+        Ok(Value::Map(alloc::vec![(
+            Value::from(variant),
+            value.serialize(self)?
+        )]))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, SerdeError> {
+        Ok(SerializeList(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeList>, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, SerdeError> {
+        Ok(SerializeMap {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeMap>, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct SerializeList(Vec<Value>);
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.0.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Value::List(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeMap {
+    entries: Vec<(Value, Value)>,
+    next_key: Option<Value>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.next_key = Some(to_value(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| ser::Error::custom("`serialize_value` called before `serialize_key`"))?;
+        self.entries.push((key, to_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Value::Map(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.entries.push((Value::from(key), to_value(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<S> SerializeVariant<S> {
+    fn wrap(variant: &'static str, value: Value) -> Value {
+        Value::Map(alloc::vec![(Value::from(variant), value)])
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Self::wrap(
+            self.variant,
+            ser::SerializeSeq::end(self.inner)?,
+        ))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeMap> {
+    type Ok = Value;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(Self::wrap(
+            self.variant,
+            ser::SerializeMap::end(self.inner)?,
+        ))
+    }
+}
+
+// deserializer
+
+impl IntoDeserializer<'_, SerdeError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Unit => visitor.visit_unit(),
+            Self::Bool(v) => visitor.visit_bool(v),
+            Self::Int(v) => visitor.visit_i64(v),
+            Self::UInt(v) => visitor.visit_u64(v),
+            Self::Float(v) => visitor.visit_f64(v),
+            Self::Bytes(v) => visitor.visit_bytes(&v),
+            Self::String(v) => visitor.visit_string(v),
+            Self::List(values) => {
+                let mut seq = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Self::Map(entries) => {
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Unit => visitor.visit_none(),
+            Self::List(mut values) if values.len() == 1 => visitor.visit_some(values.remove(0)),
+            other => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"unit or a list with a single value",
+            )),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Self::Map(mut entries) if entries.len() == 1 => {
+                let (variant, value) = entries.remove(0);
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"a string or a map with a single entry",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct EnumDeserializer {
+    variant: Value,
+    value: Value,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = SerdeError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantDeserializer), SerdeError> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+struct VariantDeserializer(Value);
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        match self.0 {
+            Value::Unit => Ok(()),
+            other => Err(de::Error::invalid_type(other.unexpected(), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, SerdeError> {
+        seed.deserialize(self.0)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.0.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.0.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        alloc::collections::BTreeMap,
+        serde::{Deserialize, Serialize},
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Event {
+        Quit,
+        Move(i32, i32),
+        Chat { channel: u16, text: String },
+        Ping(u64),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Packet {
+        id: u32,
+        name: Option<String>,
+        tags: Vec<String>,
+        scores: BTreeMap<String, f32>,
+        events: Vec<Event>,
+        unit: (),
+    }
+
+    fn round_trip<T>(value: &T) -> Value
+    where
+        T: fmt::Debug + PartialEq + Serialize + DeserializeOwned,
+    {
+        let dynamic = to_value(value).unwrap();
+        assert_eq!(*value, from_value::<T>(dynamic.clone()).unwrap());
+        // and the value itself goes through serde unchanged
+        assert_eq!(dynamic, from_value::<Value>(dynamic.clone()).unwrap());
+        dynamic
+    }
+
+    #[test]
+    fn round_trip_structs() {
+        round_trip(&Packet {
+            id: 5,
+            name: Some("alice".into()),
+            tags: vec!["a".into(), "b".into()],
+            scores: BTreeMap::from([("x".into(), 1.5), ("y".into(), -2.0)]),
+            events: vec![
+                Event::Quit,
+                Event::Move(-1, 2),
+                Event::Chat {
+                    channel: 3,
+                    text: "hi".into(),
+                },
+                Event::Ping(u64::MAX),
+            ],
+            unit: (),
+        });
+        round_trip(&Packet {
+            id: 0,
+            name: None,
+            tags: vec![],
+            scores: BTreeMap::new(),
+            events: vec![],
+            unit: (),
+        });
+    }
+
+    #[test]
+    fn enum_shapes() {
+        assert_eq!(Value::from("Quit"), round_trip(&Event::Quit));
+        assert_eq!(
+            Value::Map(vec![(Value::from("Ping"), Value::from(3u64))]),
+            round_trip(&Event::Ping(3))
+        );
+        assert_eq!(
+            Value::Map(vec![(
+                Value::from("Move"),
+                Value::List(vec![Value::from(1i32), Value::from(2i32)])
+            )]),
+            round_trip(&Event::Move(1, 2))
+        );
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(Value::Unit, round_trip(&None::<u32>));
+        assert_eq!(
+            Value::List(vec![Value::from(5u32)]),
+            round_trip(&Some(5u32))
+        );
+        assert_eq!(Value::List(vec![Value::Unit]), round_trip(&Some(())));
+        assert_eq!(
+            Value::List(vec![Value::Unit]),
+            round_trip(&Some(None::<u32>))
+        );
+        assert_eq!(
+            Value::List(vec![Value::List(vec![Value::from(5u32)])]),
+            round_trip(&Some(Some(5u32)))
+        );
+        round_trip(&Some(vec![1u8]));
+
+        // a bare value is not an option
+        from_value::<Option<u32>>(Value::from(5u32)).unwrap_err();
+        from_value::<Option<u32>>(Value::List(vec![])).unwrap_err();
+    }
+
+    #[test]
+    fn wrong_shape() {
+        from_value::<Packet>(Value::from(1u8)).unwrap_err();
+        from_value::<Event>(Value::List(vec![])).unwrap_err();
+        from_value::<Event>(Value::from("Unknown")).unwrap_err();
+        from_value::<u8>(Value::from(300u16)).unwrap_err();
+        from_value::<(u8, u8)>(Value::List(vec![Value::from(1u8)])).unwrap_err();
+    }
+
+    #[test]
+    fn integers_convert() {
+        assert_eq!(5u8, from_value::<u8>(Value::from(5i64)).unwrap());
+        assert_eq!(-5i8, from_value::<i8>(Value::from(-5i64)).unwrap());
+        from_value::<u8>(Value::from(-5i64)).unwrap_err();
+    }
+}
@@ -0,0 +1,219 @@
+use {
+    super::{CanonicalVarIntError, NonCanonicalVarInt, VarInt, VarIntTooLarge},
+    crate::{BufTooShortOr, Decode, Encode, EncodeLen, FixedEncodeLenHint, Read, Write},
+    core::convert::Infallible,
+};
+
+/// [`VarInt`] which only accepts the canonical (shortest) encoding of an
+/// integer when decoding.
+///
+/// [`VarInt`] decoding is lenient: it accepts overlong encodings such as
+/// `0x80 0x00` for `0`, and silently drops bits in the last byte which don't
+/// fit into the integer type. This means that many byte sequences decode to
+/// the same value. When the exact bytes matter, such as for signed or
+/// content-addressed messages, use this type instead, which fails to decode
+/// these sequences with [`NonCanonicalVarInt`].
+///
+/// Both types encode values in exactly the same way, so the two are
+/// compatible on the wire for canonical encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalVarInt<T>(pub T);
+
+macro_rules! impl_base {
+    ($ty:ty) => {
+        impl From<$ty> for CanonicalVarInt<$ty> {
+            fn from(value: $ty) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<CanonicalVarInt<$ty>> for $ty {
+            fn from(value: CanonicalVarInt<$ty>) -> Self {
+                value.0
+            }
+        }
+
+        impl From<VarInt<$ty>> for CanonicalVarInt<$ty> {
+            fn from(value: VarInt<$ty>) -> Self {
+                Self(value.0)
+            }
+        }
+
+        impl From<CanonicalVarInt<$ty>> for VarInt<$ty> {
+            fn from(value: CanonicalVarInt<$ty>) -> Self {
+                Self(value.0)
+            }
+        }
+
+        impl FixedEncodeLenHint for CanonicalVarInt<$ty> {
+            const MIN_ENCODE_LEN: usize = VarInt::<$ty>::MIN_ENCODE_LEN;
+
+            const MAX_ENCODE_LEN: usize = VarInt::<$ty>::MAX_ENCODE_LEN;
+        }
+
+        impl EncodeLen for CanonicalVarInt<$ty> {
+            #[inline]
+            fn encode_len(&self) -> usize {
+                VarInt(self.0).encode_len()
+            }
+        }
+
+        impl Encode for CanonicalVarInt<$ty> {
+            type Error = Infallible;
+
+            #[inline]
+            fn encode(&self, dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+                VarInt(self.0).encode(dst)
+            }
+        }
+    };
+}
+
+macro_rules! impl_unsigned {
+    ($ty:ty) => {
+        impl_base!($ty);
+
+        impl Decode for CanonicalVarInt<$ty> {
+            type Error = CanonicalVarIntError;
+
+            #[inline]
+            fn decode(mut buf: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                const BITS: usize = <$ty>::BITS as usize;
+
+                let mut value: $ty = 0;
+                for shift in 0..Self::MAX_ENCODE_LEN {
+                    let byte = buf.read::<u8>()?;
+                    let without_msb = byte & 0b0111_1111;
+                    // bits of this byte which would land above the top of `$ty`
+                    let bits_left = BITS - shift * 7;
+                    if bits_left < 7 && without_msb >> bits_left != 0 {
+                        return Err(CanonicalVarIntError::NonCanonical(NonCanonicalVarInt).into());
+                    }
+                    value |= <$ty>::from(without_msb) << shift * 7;
+
+                    if byte & 0b1000_0000 == 0 {
+                        if byte == 0 && shift > 0 {
+                            return Err(
+                                CanonicalVarIntError::NonCanonical(NonCanonicalVarInt).into()
+                            );
+                        }
+                        return Ok(Self(value));
+                    }
+                }
+                Err(CanonicalVarIntError::TooLarge(VarIntTooLarge).into())
+            }
+        }
+    };
+}
+
+impl_unsigned!(usize);
+impl_unsigned!(u8);
+impl_unsigned!(u16);
+impl_unsigned!(u32);
+impl_unsigned!(u64);
+
+macro_rules! impl_signed {
+    ($ty:ty, $un:ty) => {
+        impl_base!($ty);
+
+        impl Decode for CanonicalVarInt<$ty> {
+            type Error = CanonicalVarIntError;
+
+            #[inline]
+            fn decode(mut buf: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                let CanonicalVarInt(value) = buf.read::<CanonicalVarInt<$un>>()?;
+                Ok(Self(VarInt::<$ty>::zigzag_decode(value)))
+            }
+        }
+    };
+}
+
+impl_signed!(isize, usize);
+impl_signed!(i8, u8);
+impl_signed!(i16, u16);
+impl_signed!(i32, u32);
+impl_signed!(i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test::*};
+
+    fn non_canonical<T>(mut bytes: &[u8])
+    where
+        CanonicalVarInt<T>: Decode<Error = CanonicalVarIntError> + core::fmt::Debug,
+    {
+        assert!(
+            matches!(
+                bytes.read::<CanonicalVarInt<T>>(),
+                Err(BufTooShortOr::Or(CanonicalVarIntError::NonCanonical(_)))
+            ),
+            "bytes: {bytes:02x?}"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for v in [0, 1, 127, 128, 300, u64::MAX - 1, u64::MAX] {
+            hint_round_trip(&CanonicalVarInt(v));
+        }
+        for v in [0, -1, 63, -64, 64, i64::MIN, i64::MAX] {
+            hint_round_trip(&CanonicalVarInt(v));
+        }
+        for v in 0..=u8::MAX {
+            hint_round_trip(&CanonicalVarInt(v));
+        }
+        for v in 0..=u16::MAX {
+            hint_round_trip(&CanonicalVarInt(v));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(CanonicalVarInt(300u32), "ac 02");
+        crate::assert_encodes!(CanonicalVarInt(-65i32), "81 01");
+    }
+
+    #[test]
+    fn reject_overlong() {
+        non_canonical::<u32>(&[0x80, 0x00]);
+        non_canonical::<u32>(&[0xff, 0x80, 0x00]);
+        non_canonical::<u64>(&[0x80, 0x80, 0x80, 0x00]);
+        non_canonical::<i32>(&[0x81, 0x00]);
+
+        // the lenient `VarInt` accepts these
+        assert_eq!(0, (&[0x80, 0x00][..]).read::<VarInt<u32>>().unwrap().0);
+    }
+
+    #[test]
+    fn reject_overflow_bits() {
+        // u8::MAX is `ff 01`
+        non_canonical::<u8>(&[0xff, 0x03]);
+        non_canonical::<u8>(&[0xff, 0x7f]);
+        // u16::MAX is `ff ff 03`
+        non_canonical::<u16>(&[0xff, 0xff, 0x07]);
+        // u32::MAX is `ff ff ff ff 0f`
+        non_canonical::<u32>(&[0xff, 0xff, 0xff, 0xff, 0x1f]);
+        // u64::MAX is `ff ff ff ff ff ff ff ff ff 01`
+        non_canonical::<u64>(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]);
+
+        // the lenient `VarInt` accepts these
+        assert_eq!(u8::MAX, (&[0xff, 0x03][..]).read::<VarInt<u8>>().unwrap().0);
+    }
+
+    #[test]
+    fn reject_too_large() {
+        assert!(matches!(
+            (&[0x80, 0x80, 0x80][..]).read::<CanonicalVarInt<u8>>(),
+            Err(BufTooShortOr::Or(CanonicalVarIntError::TooLarge(_)))
+        ));
+    }
+
+    #[test]
+    fn decode_malformed() {
+        for v in [0, 1, 127, 128, 300, u64::MAX] {
+            truncated(&CanonicalVarInt(v));
+            byte_flips(&CanonicalVarInt(v));
+        }
+    }
+}
@@ -25,3 +25,57 @@ impl Display for VarIntTooLarge {
 impl BufError for VarIntTooLarge {}
 
 impl Error for VarIntTooLarge {}
+
+/// Attempted to read a [`CanonicalVarInt`] from a buffer, but the bytes were
+/// not the shortest possible encoding of the integer.
+///
+/// This happens when either:
+/// * the last byte is `0x00`, but it is not the only byte - the integer could
+///   have been encoded in fewer bytes, e.g. `0x80 0x00` for `0`
+/// * the last possible byte for this integer type has bits set which do not
+///   fit into the type, e.g. `0xff 0x03` for a `u8`
+///
+/// [`CanonicalVarInt`]: crate::CanonicalVarInt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonCanonicalVarInt;
+
+impl Display for NonCanonicalVarInt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "non-canonical varint")
+    }
+}
+
+impl BufError for NonCanonicalVarInt {}
+
+impl Error for NonCanonicalVarInt {}
+
+/// Error when decoding a [`CanonicalVarInt`].
+///
+/// [`CanonicalVarInt`]: crate::CanonicalVarInt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CanonicalVarIntError {
+    /// See [`VarIntTooLarge`].
+    TooLarge(VarIntTooLarge),
+    /// See [`NonCanonicalVarInt`].
+    NonCanonical(NonCanonicalVarInt),
+}
+
+impl Display for CanonicalVarIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLarge(err) => write!(f, "{err}"),
+            Self::NonCanonical(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl BufError for CanonicalVarIntError {}
+
+impl Error for CanonicalVarIntError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TooLarge(err) => Some(err),
+            Self::NonCanonical(err) => Some(err),
+        }
+    }
+}
@@ -3,15 +3,19 @@ use {
     core::{convert::Infallible, mem::size_of},
 };
 
+mod canonical;
 mod error;
 
-pub use error::*;
+pub use {canonical::*, error::*};
 
 /// Integer which is encoded in a variable amount of bytes.
 ///
 /// See the [*Protocol Buffers Documentation*] on an explanation of what
 /// varints are, and how they are encoded.
 ///
+/// Decoding is lenient, and accepts some non-canonical encodings of an integer.
+/// See [`CanonicalVarInt`] for a strict alternative.
+///
 /// [*Protocol Buffers Documentation*]: https://protobuf.dev/programming-guides/encoding/#varints
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct VarInt<T>(pub T);
@@ -61,6 +65,34 @@ macro_rules! impl_unsigned {
 
             #[inline]
             fn decode(mut buf: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+                // fast paths: if the whole varint is contiguous, decode it
+                // straight from the chunk, without going through `Buf` for
+                // every byte
+                //
+                // this stays branchy on purpose - when varints have similar
+                // lengths, the branches predict well, so the next varint can
+                // start decoding before this one has finished
+                let chunk = buf.chunk();
+                // most varints are a single byte
+                if let Some(&byte) = chunk.first() {
+                    if byte & 0b1000_0000 == 0 {
+                        buf.advance(1);
+                        return Ok(VarInt(<$ty>::from(byte)));
+                    }
+                }
+                if let Some(bytes) = chunk.first_chunk::<{ Self::MAX_ENCODE_LEN }>() {
+                    let mut value: $ty = 0;
+                    for (i, &byte) in bytes.iter().enumerate() {
+                        value |= <$ty>::from(byte & 0b0111_1111) << i * 7;
+                        if byte & 0b1000_0000 == 0 {
+                            buf.advance(i + 1);
+                            return Ok(VarInt(value));
+                        }
+                    }
+                    buf.advance(Self::MAX_ENCODE_LEN);
+                    return Err(VarIntTooLarge.into());
+                }
+
                 let mut value: $ty = 0;
                 for shift in 0..Self::MAX_ENCODE_LEN {
                     let byte = buf.read::<u8>()?;
@@ -112,7 +144,7 @@ macro_rules! impl_signed {
             #[inline]
             #[allow(clippy::cast_possible_wrap)]
             #[allow(clippy::cast_sign_loss)]
-            const fn zigzag_encode(v: $ty) -> $un {
+            pub(crate) const fn zigzag_encode(v: $ty) -> $un {
                 const BITS: u32 = <$ty>::BITS;
                 ((v << 1) ^ (v >> (BITS - 1))) as $un
             }
@@ -120,7 +152,7 @@ macro_rules! impl_signed {
             #[inline]
             #[allow(clippy::cast_possible_wrap)]
             #[allow(clippy::cast_sign_loss)]
-            const fn zigzag_decode(v: $un) -> $ty {
+            pub(crate) const fn zigzag_decode(v: $un) -> $ty {
                 ((v >> 1) ^ (-((v & 1) as $ty)) as $un) as $ty
             }
         }
@@ -191,6 +223,99 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(VarInt(0u32), "00");
+        crate::assert_encodes!(VarInt(1u32), "01");
+        crate::assert_encodes!(VarInt(127u32), "7f");
+        crate::assert_encodes!(VarInt(128u32), "80 01");
+        crate::assert_encodes!(VarInt(300u32), "ac 02");
+        crate::assert_encodes!(VarInt(u32::MAX), "ff ff ff ff 0f");
+        crate::assert_encodes!(VarInt(-1i32), "01");
+        crate::assert_encodes!(VarInt(1i32), "02");
+        crate::assert_encodes!(VarInt(-65i32), "81 01");
+    }
+
+    #[test]
+    fn decode_malformed() {
+        for v in [0, 1, 127, 128, 300, u64::MAX] {
+            truncated(&VarInt(v));
+            byte_flips(&VarInt(v));
+        }
+        for v in [0, -1, 63, -64, 64, i64::MIN, i64::MAX] {
+            truncated(&VarInt(v));
+            byte_flips(&VarInt(v));
+        }
+    }
+
+    /// Buffer which only exposes one byte at a time, forcing the slow path.
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Buf for OneByte<'_> {
+        fn remaining(&self) -> usize {
+            self.0.len()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            &self.0[..self.0.len().min(1)]
+        }
+
+        fn advance(&mut self, cnt: usize) {
+            self.0.advance(cnt);
+        }
+    }
+
+    #[test]
+    fn fast_path_matches_slow_path() {
+        fn check<T>(bytes: &[u8])
+        where
+            VarInt<T>: Decode<Error = VarIntTooLarge> + core::fmt::Debug + PartialEq,
+        {
+            let mut fast = bytes;
+            let mut slow = OneByte(bytes);
+            let fast_result = fast.read::<VarInt<T>>();
+            let slow_result = slow.read::<VarInt<T>>();
+            assert_eq!(slow_result, fast_result, "bytes: {bytes:02x?}");
+            assert_eq!(slow.remaining(), fast.remaining(), "bytes: {bytes:02x?}");
+        }
+
+        fn check_all(bytes: &[u8]) {
+            check::<u8>(bytes);
+            check::<u16>(bytes);
+            check::<u32>(bytes);
+            check::<u64>(bytes);
+            check::<usize>(bytes);
+        }
+
+        // varints of every length, including overlong ones
+        for len in 1..=12 {
+            let mut bytes = [0xffu8; 16];
+            bytes[len - 1] = 0x7f;
+            check_all(&bytes);
+            bytes[len - 1] = 0x01;
+            check_all(&bytes);
+        }
+
+        // xorshift64
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let mut bytes = [0u8; 16];
+            bytes[..8].copy_from_slice(&state.to_le_bytes());
+            // bias towards having the continuation bit set, to get longer varints
+            for (i, b) in bytes.iter_mut().enumerate() {
+                if (state >> (i + 8)) & 0b11 != 0 {
+                    *b |= 0x80;
+                }
+            }
+            check_all(&bytes);
+            check_all(&bytes[..8]);
+        }
+    }
+
     #[test]
     fn decode_all_msbs() {
         const LEN: usize = 64;
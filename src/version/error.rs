@@ -0,0 +1,56 @@
+use {
+    crate::BufError,
+    core::{error::Error, fmt::Display},
+};
+
+/// Attempted to read a [`Versioned`] value from a buffer, but the envelope
+/// around the value was malformed.
+///
+/// This may mean that:
+/// * the version or body length could not be read
+/// * the body ended before the inner value could be fully decoded
+///
+/// [`Versioned`]: crate::version::Versioned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidEnvelope;
+
+impl Display for InvalidEnvelope {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid versioned envelope")
+    }
+}
+
+impl BufError for InvalidEnvelope {}
+
+impl Error for InvalidEnvelope {}
+
+/// Error when decoding a [`Versioned`] value.
+///
+/// [`Versioned`]: crate::version::Versioned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionedError<E> {
+    /// Failed to decode the inner value.
+    Inner(E),
+    /// See [`InvalidEnvelope`].
+    Invalid(InvalidEnvelope),
+}
+
+impl<E: Display> Display for VersionedError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Inner(err) => write!(f, "{err}"),
+            Self::Invalid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E> BufError for VersionedError<E> {}
+
+impl<E: Error + 'static> Error for VersionedError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Inner(err) => Some(err),
+            Self::Invalid(err) => Some(err),
+        }
+    }
+}
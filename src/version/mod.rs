@@ -0,0 +1,406 @@
+//! Allows messages to evolve between protocol versions, while staying
+//! compatible with peers on older or newer versions.
+//!
+//! Wrap a value in [`Versioned`] to encode it in an envelope containing the
+//! [`Version`] it was encoded with, and the length of its body. The body is
+//! encoded and decoded with [`EncodeWith`] and [`DecodeWith`], using the
+//! envelope's [`Version`] as context.
+//!
+//! This allows two kinds of compatibility:
+//! * **Backward** - when a newer peer reads a message from an older peer,
+//!   fields added in a later version are absent. Mark these fields using
+//!   [`Version::read_since`] and [`Version::write_since`], and they will take
+//!   their default value.
+//! * **Forward** - when an older peer reads a message from a newer peer, the
+//!   body may contain trailing fields that the older peer doesn't know about.
+//!   These are skipped over, since the envelope knows the length of the body.
+//!
+//! For this to work, new fields must only ever be added to the end of a
+//! message.
+//!
+//! # Wire format
+//!
+//! * version as a [`VarInt<u32>`]
+//! * body length as a [`VarInt<usize>`]
+//! * body bytes
+//!
+//! # Examples
+//!
+//! ```
+//! use octs::{
+//!     BufTooShortOr, BytesMut, DecodeWith, EncodeWith, Read, Write,
+//!     version::{Version, Versioned},
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Player {
+//!     health: u16,
+//!     // added in version 2
+//!     armor: u16,
+//! }
+//!
+//! impl EncodeWith<Version> for Player {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn encode_with(
+//!         &self,
+//!         mut dst: impl Write,
+//!         version: &Version,
+//!     ) -> Result<(), BufTooShortOr<Self::Error>> {
+//!         dst.write(self.health)?;
+//!         version.write_since(2, &mut dst, &self.armor)?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! impl DecodeWith<Version> for Player {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn decode_with(
+//!         mut src: impl Read,
+//!         version: &Version,
+//!     ) -> Result<Self, BufTooShortOr<Self::Error>> {
+//!         Ok(Self {
+//!             health: src.read()?,
+//!             armor: version.read_since(2, &mut src)?,
+//!         })
+//!     }
+//! }
+//!
+//! // an old peer on version 1 sends a message...
+//! let mut buf = BytesMut::new();
+//! let player = Player {
+//!     health: 100,
+//!     armor: 50,
+//! };
+//! buf.write(Versioned::new(Version(1), player)).unwrap();
+//!
+//! // ...and we read it on version 2
+//! let Versioned { version, value } = buf.freeze().read::<Versioned<Player>>().unwrap();
+//! assert_eq!(Version(1), version);
+//! assert_eq!(
+//!     Player {
+//!         health: 100,
+//!         armor: 0
+//!     },
+//!     value
+//! );
+//! ```
+//!
+//! [`EncodeWith`]: crate::EncodeWith
+//! [`DecodeWith`]: crate::DecodeWith
+
+mod error;
+
+pub use error::*;
+use {
+    crate::{
+        Buf, BufTooShort, BufTooShortOr, CountingWriter, Decode, DecodeWith, Encode, EncodeLen,
+        EncodeWith, Read, VarInt, Write,
+    },
+    core::convert::Infallible,
+};
+
+/// Version of a protocol that a message was encoded with.
+///
+/// This is used as the context when encoding or decoding the body of a
+/// [`Versioned`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Version(pub u32);
+
+impl Version {
+    /// Attempts to read a field which was added in version `since`.
+    ///
+    /// If this version is older than `since`, the field was not written, so
+    /// nothing is read and [`Default::default`] is returned.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the field should be present, but could not be read.
+    pub fn read_since<T: DecodeWith<Self> + Default>(
+        &self,
+        since: u32,
+        mut src: impl Read,
+    ) -> Result<T, BufTooShortOr<T::Error>> {
+        if self.0 >= since {
+            src.read_with(self)
+        } else {
+            Ok(T::default())
+        }
+    }
+
+    /// Attempts to write a field which was added in version `since`.
+    ///
+    /// If this version is older than `since`, the field is not written.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the field should be present, but could not be written.
+    pub fn write_since<T: EncodeWith<Self> + ?Sized>(
+        &self,
+        since: u32,
+        mut dst: impl Write,
+        value: &T,
+    ) -> Result<(), BufTooShortOr<T::Error>> {
+        if self.0 >= since {
+            dst.write_with(value, self)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl EncodeLen for Version {
+    #[inline]
+    fn encode_len(&self) -> usize {
+        VarInt(self.0).encode_len()
+    }
+}
+
+impl Decode for Version {
+    type Error = crate::VarIntTooLarge;
+
+    #[inline]
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let VarInt(version) = src.read()?;
+        Ok(Self(version))
+    }
+}
+
+impl Encode for Version {
+    type Error = Infallible;
+
+    #[inline]
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        dst.write(VarInt(self.0))
+    }
+}
+
+/// Wrapper around a `T` which is encoded in an envelope containing the
+/// [`Version`] it was encoded with, and the length of its body.
+///
+/// See the [module-level documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Versioned<T> {
+    /// Version that the value was encoded with.
+    pub version: Version,
+    /// Inner value.
+    pub value: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wraps a value which will be encoded with the given version.
+    pub const fn new(version: Version, value: T) -> Self {
+        Self { version, value }
+    }
+}
+
+impl<T: DecodeWith<Version>> Decode for Versioned<T> {
+    type Error = VersionedError<T::Error>;
+
+    fn decode(mut src: impl Read) -> Result<Self, BufTooShortOr<Self::Error>> {
+        let version = src
+            .read::<Version>()
+            .map_err(|e| e.map_or(|_| VersionedError::Invalid(InvalidEnvelope)))?;
+        let VarInt(len) = src
+            .read::<VarInt<usize>>()
+            .map_err(|e| e.map_or(|_| VersionedError::Invalid(InvalidEnvelope)))?;
+        if src.remaining() < len {
+            return Err(BufTooShortOr::TooShort);
+        }
+
+        let mut body = (&mut src).take(len);
+        let value = body.read_with::<T, _>(&version).map_err(|e| match e {
+            BufTooShortOr::TooShort => VersionedError::Invalid(InvalidEnvelope),
+            BufTooShortOr::Or(e) => VersionedError::Inner(e),
+        })?;
+        // skip any fields added in versions newer than what `T` knows about
+        let unknown = body.remaining();
+        src.advance(unknown);
+
+        Ok(Self { version, value })
+    }
+}
+
+impl<T: EncodeWith<Version>> Versioned<T> {
+    // the body's length depends on the version, so it can't come from an
+    // `EncodeLen` impl on `T` - instead, measure it with a dry run
+    fn body_len(&self) -> Result<usize, BufTooShortOr<T::Error>> {
+        let mut counter = CountingWriter::new();
+        counter.write_with(&self.value, &self.version)?;
+        Ok(counter.count())
+    }
+}
+
+impl<T: EncodeWith<Version>> EncodeLen for Versioned<T> {
+    fn encode_len(&self) -> usize {
+        // values which fail to encode will fail again when actually encoded,
+        // so don't panic on them like the default does
+        let len = self.body_len().unwrap_or(0);
+        self.version.encode_len() + VarInt(len).encode_len() + len
+    }
+}
+
+impl<T: EncodeWith<Version>> Encode for Versioned<T> {
+    type Error = T::Error;
+
+    fn encode(&self, mut dst: impl Write) -> Result<(), BufTooShortOr<Self::Error>> {
+        let len = VarInt(self.body_len()?);
+        if dst.remaining_mut() < self.version.encode_len() + len.encode_len() + len.0 {
+            return Err(BufTooShortOr::TooShort);
+        }
+        dst.write(self.version).map_err(BufTooShort::from)?;
+        dst.write(len).map_err(BufTooShort::from)?;
+        dst.write_with(&self.value, &self.version)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{BytesMut, StackBuf, test::*},
+    };
+
+    #[derive(Debug, Clone, PartialEq, Default)]
+    struct MessageV1 {
+        a: u16,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Default)]
+    struct MessageV2 {
+        a: u16,
+        b: u32,
+    }
+
+    impl EncodeWith<Version> for MessageV1 {
+        type Error = Infallible;
+
+        fn encode_with(
+            &self,
+            mut dst: impl Write,
+            _: &Version,
+        ) -> Result<(), BufTooShortOr<Infallible>> {
+            dst.write(self.a)
+        }
+    }
+
+    impl DecodeWith<Version> for MessageV1 {
+        type Error = Infallible;
+
+        fn decode_with(mut src: impl Read, _: &Version) -> Result<Self, BufTooShortOr<Infallible>> {
+            Ok(Self { a: src.read()? })
+        }
+    }
+
+    impl EncodeWith<Version> for MessageV2 {
+        type Error = Infallible;
+
+        fn encode_with(
+            &self,
+            mut dst: impl Write,
+            version: &Version,
+        ) -> Result<(), BufTooShortOr<Infallible>> {
+            dst.write(self.a)?;
+            version.write_since(2, &mut dst, &self.b)?;
+            Ok(())
+        }
+    }
+
+    impl DecodeWith<Version> for MessageV2 {
+        type Error = Infallible;
+
+        fn decode_with(
+            mut src: impl Read,
+            version: &Version,
+        ) -> Result<Self, BufTooShortOr<Infallible>> {
+            Ok(Self {
+                a: src.read()?,
+                b: version.read_since(2, &mut src)?,
+            })
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn wire_format() {
+        crate::assert_encodes!(
+            Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }),
+            "02 06 0001 00000002"
+        );
+        crate::assert_encodes!(
+            Versioned::new(Version(1), MessageV2 { a: 1, b: 2 }),
+            "01 02 0001"
+        );
+    }
+
+    #[test]
+    fn same_version() {
+        round_trip(&Versioned::new(Version(1), MessageV1 { a: 1 }));
+        round_trip(&Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }));
+        round_trip_in(
+            &Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }),
+            &mut [0; 8],
+        );
+    }
+
+    #[test]
+    fn backward_compatible() {
+        let mut buf = BytesMut::new();
+        buf.write(Versioned::new(Version(1), MessageV1 { a: 1 }))
+            .unwrap();
+        let mut buf = buf.freeze();
+        assert_eq!(
+            Versioned::new(Version(1), MessageV2 { a: 1, b: 0 }),
+            buf.read::<Versioned<MessageV2>>().unwrap()
+        );
+        assert!(!buf.has_remaining());
+    }
+
+    #[test]
+    fn forward_compatible() {
+        let mut buf = BytesMut::new();
+        buf.write(Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }))
+            .unwrap();
+        buf.write(0xffu8).unwrap();
+        let mut buf = buf.freeze();
+        assert_eq!(
+            Versioned::new(Version(2), MessageV1 { a: 1 }),
+            buf.read::<Versioned<MessageV1>>().unwrap()
+        );
+        // only the trailing byte after the envelope is left
+        assert_eq!(&[0xff], &buf[..]);
+    }
+
+    #[test]
+    fn encode_too_short() {
+        let mut buf = StackBuf::<7>::new();
+        assert_eq!(
+            Err(BufTooShortOr::TooShort),
+            buf.write(Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }))
+        );
+        assert!(buf.is_empty());
+
+        let mut buf = StackBuf::<8>::new();
+        buf.write(Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }))
+            .unwrap();
+        assert_eq!(&[0x02, 0x06, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02], &buf[..]);
+    }
+
+    #[test]
+    fn body_too_short() {
+        // declares a 1 byte body, but `a` needs 2
+        let mut buf = &[0x01, 0x01, 0x00, 0x01][..];
+        assert_eq!(
+            BufTooShortOr::Or(VersionedError::Invalid(InvalidEnvelope)),
+            buf.read::<Versioned<MessageV1>>().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn decode_malformed() {
+        truncated(&Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }));
+        byte_flips(&Versioned::new(Version(2), MessageV2 { a: 1, b: 2 }));
+    }
+}
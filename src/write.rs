@@ -1,5 +1,5 @@
 use {
-    crate::{BufTooShort, BufTooShortOr},
+    crate::{BufTooShort, BufTooShortOr, CountingWriter, prim::Number},
     bytes::{Buf, BufMut},
 };
 
@@ -26,12 +26,49 @@ pub trait Write: BufMut {
         }
     }
 
+    /// Attempts to write all `values` into the next bytes in the buffer.
+    ///
+    /// This is equivalent to writing each value of `T` individually, but
+    /// checks that there is enough space left only once, and encodes values in
+    /// blocks.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are not enough bytes in this buffer left for writing all
+    /// of `values` into, in which case nothing is written.
+    #[inline]
+    fn write_slice<T: Number>(&mut self, values: &[T]) -> Result<(), BufTooShort> {
+        // number of bytes buffered on the stack at once
+        const BLOCK_LEN: usize = 256;
+
+        let len = values.len().checked_mul(T::ENCODE_LEN).ok_or(BufTooShort)?;
+        if self.remaining_mut() < len {
+            return Err(BufTooShort);
+        }
+
+        let mut block = [0u8; BLOCK_LEN];
+        for values in values.chunks(BLOCK_LEN / T::ENCODE_LEN) {
+            let dst = &mut block[..values.len() * T::ENCODE_LEN];
+            T::encode_slice(values, dst);
+            self.put_slice(dst);
+        }
+        Ok(())
+    }
+
     /// Attempts to write a `T` into the next bytes in the buffer.
     ///
+    /// This does not check that `T`'s [`EncodeLen::encode_len`] matches the
+    /// number of bytes written, since `T` is only required to be [`Encode`],
+    /// and there is no way to run the check only for `T`s which also implement
+    /// [`EncodeLen`] without specialization. To check it in debug builds, write
+    /// the value wrapped in a [`CheckEncodeLen`] instead.
+    ///
     /// # Errors
     ///
     /// Errors if there are not enough bytes in this buffer left for writing
     /// into, or if `value` could not be encoded into bytes.
+    ///
+    /// [`CheckEncodeLen`]: crate::CheckEncodeLen
     #[inline]
     fn write<T: Encode>(&mut self, value: T) -> Result<(), BufTooShortOr<T::Error>>
     where
@@ -39,6 +76,28 @@ pub trait Write: BufMut {
     {
         value.encode(self)
     }
+
+    /// Attempts to write a `T` into the next bytes in the buffer, using `ctx`
+    /// as context.
+    ///
+    /// Any [`Encode`] type can also be written with this function, in which
+    /// case `ctx` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there are not enough bytes in this buffer left for writing
+    /// into, or if `value` could not be encoded into bytes.
+    #[inline]
+    fn write_with<T: EncodeWith<C> + ?Sized, C: ?Sized>(
+        &mut self,
+        value: &T,
+        ctx: &C,
+    ) -> Result<(), BufTooShortOr<T::Error>>
+    where
+        Self: Sized,
+    {
+        value.encode_with(self, ctx)
+    }
 }
 
 impl<T: BufMut + ?Sized> Write for T {}
@@ -67,13 +126,70 @@ impl<T: Encode + ?Sized> Encode for &T {
     }
 }
 
+/// Allows writing a value of this type into a [`Write`], using some context of
+/// type `C`.
+///
+/// Use this instead of [`Encode`] when how a value is encoded depends on some
+/// state outside of the buffer, such as a negotiated protocol version, or a
+/// string table shared between the two sides of a connection.
+///
+/// All [`Encode`] types implement this trait for any `C`, ignoring the
+/// context.
+pub trait EncodeWith<C: ?Sized> {
+    /// Error type of [`EncodeWith::encode_with`], excluding [`BufTooShort`]
+    /// errors.
+    type Error;
+
+    /// Attempts to encode a value of this type into a [`Write`], using `ctx` as
+    /// context.
+    ///
+    /// # Errors
+    ///
+    /// If there are not enough bytes left for writing into,
+    /// [`BufTooShortOr::TooShort`] is returned. Otherwise, it is up to the
+    /// implementation on what the returned error represents.
+    fn encode_with(&self, dst: impl Write, ctx: &C) -> Result<(), BufTooShortOr<Self::Error>>;
+}
+
+impl<T: Encode + ?Sized, C: ?Sized> EncodeWith<C> for T {
+    type Error = T::Error;
+
+    #[inline]
+    fn encode_with(&self, dst: impl Write, _: &C) -> Result<(), BufTooShortOr<Self::Error>> {
+        self.encode(dst)
+    }
+}
+
 /// Gets how many bytes it takes to encode a value of this type.
+///
+/// To check that a hand-written implementation matches [`Encode`], wrap values
+/// in a [`CheckEncodeLen`] when writing them.
+///
+/// [`CheckEncodeLen`]: crate::CheckEncodeLen
 pub trait EncodeLen: Encode {
     /// Gets how many bytes it takes to encode this value into a [`Write`].
     ///
     /// If this function returns `n`, then if you [`Encode::encode`] this value
     /// into a buffer, it is guaranteed that `n` bytes will be consumed.
-    fn encode_len(&self) -> usize;
+    ///
+    /// By default, this encodes the value into a [`CountingWriter`] and returns
+    /// how many bytes were written. This is always correct, but does the work
+    /// of encoding the value twice, so override it if the length can be
+    /// computed more cheaply.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics if the value fails to encode, since
+    /// it has no meaningful length. Override this if values of your type can
+    /// fail to encode.
+    fn encode_len(&self) -> usize {
+        let mut counter = CountingWriter::new();
+        assert!(
+            self.encode(&mut counter).is_ok(),
+            "cannot get `encode_len` of a value which fails to encode"
+        );
+        counter.count()
+    }
 }
 
 /// Provides hints on how many bytes it may take to encode a value of this type.